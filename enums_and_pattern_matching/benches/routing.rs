@@ -0,0 +1,139 @@
+// Times building a routing table, looking addresses up in it and
+// taking routes out and putting them back.
+// Run with: cargo bench --bench routing
+// It uses only the standard library, so it prints plain timings instead of
+// the statistics a benchmarking crate would give.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use enums_and_pattern_matching::ip::IpAddrKind;
+use enums_and_pattern_matching::routing::{RoutingTable, Subnet};
+
+const V4_ROUTES: usize = 400_000;
+const V6_ROUTES: usize = 100_000;
+const LOOKUPS: usize = 1_000_000;
+
+// A small xorshift generator, so every run uses the same addresses.
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn v4(&mut self) -> IpAddrKind {
+        IpAddrKind::V4(self.next() as u32)
+    }
+
+    fn v6(&mut self) -> IpAddrKind {
+        // Global unicast, 2000::/3, like most of a real IPv6 table.
+        let bits = (self.next() as u128) << 64 | self.next() as u128;
+        IpAddrKind::V6(bits >> 3 | 1 << 125)
+    }
+}
+
+fn per_item(nanos: u128, count: usize) -> f64 {
+    nanos as f64 / count as f64
+}
+
+fn main() {
+    let mut random = Random(0x9e37_79b9_7f4a_7c15);
+
+    // Prefix lengths roughly as in a real table: mostly /24 for IPv4, /48 for IPv6.
+    let mut subnets = Vec::with_capacity(V4_ROUTES + V6_ROUTES);
+    for _ in 0..V4_ROUTES {
+        let length = [8, 16, 20, 22, 24, 24, 24, 24][random.next() as usize % 8];
+        subnets.push(Subnet::new(random.v4(), length).unwrap());
+    }
+    for _ in 0..V6_ROUTES {
+        let length = [32, 36, 40, 44, 48, 48, 48, 64][random.next() as usize % 8];
+        subnets.push(Subnet::new(random.v6(), length).unwrap());
+    }
+
+    let start = Instant::now();
+    let mut table = RoutingTable::new();
+    for (index, subnet) in subnets.iter().enumerate() {
+        table.insert(*subnet, format!("hop{}", index % 64));
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "insert: {} routes in {:.1?} ({:.0} ns per route)",
+        table.len(),
+        elapsed,
+        per_item(elapsed.as_nanos(), subnets.len())
+    );
+
+    for (family, make) in [
+        ("IPv4", Random::v4 as fn(&mut Random) -> IpAddrKind),
+        ("IPv6", Random::v6),
+    ] {
+        let addresses: Vec<IpAddrKind> = (0..LOOKUPS).map(|_| make(&mut random)).collect();
+        let start = Instant::now();
+        let mut found = 0;
+        for address in &addresses {
+            if black_box(table.lookup(black_box(address))).is_some() {
+                found += 1;
+            }
+        }
+        let elapsed = start.elapsed();
+        println!(
+            "lookup {family}: {LOOKUPS} addresses in {:.1?} ({:.0} ns per lookup, {found} routed)",
+            elapsed,
+            per_item(elapsed.as_nanos(), LOOKUPS)
+        );
+    }
+
+    // Churn: every route is taken out and put back, so the trie is pruned and
+    // its freed nodes are reused. The node count should end where it started.
+    let nodes = table.node_count();
+    let start = Instant::now();
+    for subnet in &subnets {
+        if let Some(next_hop) = table.remove(black_box(subnet)) {
+            table.insert(*subnet, next_hop);
+        }
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "remove and reinsert: {} routes in {:.1?} ({:.0} ns per route, {} nodes before, {} after)",
+        subnets.len(),
+        elapsed,
+        per_item(elapsed.as_nanos(), subnets.len()),
+        nodes,
+        table.node_count()
+    );
+
+    let start = Instant::now();
+    for subnet in &subnets {
+        table.remove(black_box(subnet));
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "remove: {} routes in {:.1?} ({:.0} ns per route, {} nodes left)",
+        subnets.len(),
+        elapsed,
+        per_item(elapsed.as_nanos(), subnets.len()),
+        table.node_count()
+    );
+
+    // For comparison: checking every route, on a few addresses only.
+    let sample = 200;
+    let start = Instant::now();
+    for _ in 0..sample {
+        let address = random.v4();
+        black_box(
+            subnets
+                .iter()
+                .filter(|subnet| subnet.contains(&address))
+                .max_by_key(|subnet| subnet.prefix_len()),
+        );
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "linear scan: {:.0} ns per lookup",
+        per_item(elapsed.as_nanos(), sample)
+    );
+}
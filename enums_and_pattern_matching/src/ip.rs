@@ -0,0 +1,254 @@
+// **** IP Addresses ****
+// An IpAddrKind holds a checked address as a number: the 32 bits of an IPv4
+// address or the 128 bits of an IPv6 address, most significant bit first.
+// Addresses are made by parsing text, or from their octets or segments.
+//
+// IPv4 is read in dotted-quad form, 192.168.0.1: four numbers from 0 to 255,
+// without leading zeros, which some tools read as octal.
+// IPv6 is read as eight groups of one to four hex digits, where one run of
+// groups may be left out as "::" and the last two may be written as an
+// IPv4 address: 2001:db8::1, ::ffff:192.0.2.1.
+//
+// Addresses are printed in canonical form: IPv4 as dotted-quad, IPv6 as in
+// RFC 5952, lowercase with the longest run of zero groups shortened to "::".
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IpAddrKind {
+    V4(u32),
+    V6(u128),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IpParseError {
+    // `reason` says what is wrong with the text.
+    InvalidV4 { text: String, reason: &'static str },
+    InvalidV6 { text: String, reason: &'static str },
+}
+
+impl fmt::Display for IpParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IpParseError::InvalidV4 { text, reason } => {
+                write!(f, "'{text}' is not a valid IPv4 address: {reason}")
+            }
+            IpParseError::InvalidV6 { text, reason } => {
+                write!(f, "'{text}' is not a valid IPv6 address: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for IpParseError {}
+
+fn parse_octets(text: &str) -> Result<[u8; 4], &'static str> {
+    let mut octets = [0u8; 4];
+    let mut parts = text.split('.');
+    for octet in &mut octets {
+        let part = parts
+            .next()
+            .ok_or("it needs four numbers separated by dots")?;
+        if part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()) {
+            return Err("every part must be a number");
+        }
+        if part.len() > 1 && part.starts_with('0') {
+            return Err("a number may not have a leading zero");
+        }
+        *octet = part
+            .parse()
+            .map_err(|_| "every number must be between 0 and 255")?;
+    }
+    if parts.next().is_some() {
+        return Err("it needs four numbers separated by dots");
+    }
+    Ok(octets)
+}
+
+// The groups of one side of a "::", or of a whole address without one.
+// Only the last group of an address may be an IPv4 address.
+fn parse_groups(text: &str, ends_address: bool) -> Result<Vec<u16>, &'static str> {
+    let mut groups = Vec::new();
+    if text.is_empty() {
+        return Ok(groups);
+    }
+    let parts: Vec<&str> = text.split(':').collect();
+    for (index, part) in parts.iter().enumerate() {
+        if part.contains('.') {
+            if !(ends_address && index == parts.len() - 1) {
+                return Err("an IPv4 address may only be the last part");
+            }
+            let [a, b, c, d] = parse_octets(part)?;
+            groups.push(u16::from_be_bytes([a, b]));
+            groups.push(u16::from_be_bytes([c, d]));
+        } else if part.is_empty() {
+            return Err("groups are separated by a single ':', except for one '::'");
+        } else if part.len() > 4 || !part.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err("every group must be one to four hex digits");
+        } else {
+            groups.push(u16::from_str_radix(part, 16).expect("checked hex digits"));
+        }
+    }
+    Ok(groups)
+}
+
+fn parse_segments(text: &str) -> Result<[u16; 8], &'static str> {
+    let groups = match text.split_once("::") {
+        None => {
+            let groups = parse_groups(text, true)?;
+            if groups.len() != 8 {
+                return Err("it needs eight groups, or '::' for the ones left out");
+            }
+            groups
+        }
+        Some((head, tail)) => {
+            if tail.contains("::") {
+                return Err("'::' may only appear once");
+            }
+            let head = parse_groups(head, false)?;
+            let tail = parse_groups(tail, true)?;
+            if head.len() + tail.len() > 7 {
+                return Err("'::' must stand for at least one group");
+            }
+            let mut groups = head;
+            groups.resize(8 - tail.len(), 0);
+            groups.extend(tail);
+            groups
+        }
+    };
+    let mut segments = [0u16; 8];
+    segments.copy_from_slice(&groups);
+    Ok(segments)
+}
+
+impl IpAddrKind {
+    pub fn from_octets(octets: [u8; 4]) -> IpAddrKind {
+        IpAddrKind::V4(u32::from_be_bytes(octets))
+    }
+
+    pub fn from_segments(segments: [u16; 8]) -> IpAddrKind {
+        let bits = segments
+            .iter()
+            .fold(0u128, |bits, &segment| bits << 16 | segment as u128);
+        IpAddrKind::V6(bits)
+    }
+
+    pub fn parse_v4(text: &str) -> Result<IpAddrKind, IpParseError> {
+        parse_octets(text)
+            .map(IpAddrKind::from_octets)
+            .map_err(|reason| IpParseError::InvalidV4 {
+                text: text.to_string(),
+                reason,
+            })
+    }
+
+    pub fn parse_v6(text: &str) -> Result<IpAddrKind, IpParseError> {
+        parse_segments(text)
+            .map(IpAddrKind::from_segments)
+            .map_err(|reason| IpParseError::InvalidV6 {
+                text: text.to_string(),
+                reason,
+            })
+    }
+
+    pub fn is_v4(&self) -> bool {
+        matches!(self, IpAddrKind::V4(_))
+    }
+
+    pub fn is_v6(&self) -> bool {
+        matches!(self, IpAddrKind::V6(_))
+    }
+
+    // 0.0.0.0 or ::, which stand for "no particular address".
+    pub fn is_unspecified(&self) -> bool {
+        matches!(self, IpAddrKind::V4(0) | IpAddrKind::V6(0))
+    }
+
+    // 127.0.0.0/8 or ::1.
+    pub fn is_loopback(&self) -> bool {
+        match *self {
+            IpAddrKind::V4(bits) => bits >> 24 == 127,
+            IpAddrKind::V6(bits) => bits == 1,
+        }
+    }
+
+    // 10.0.0.0/8, 172.16.0.0/12 and 192.168.0.0/16 (RFC 1918),
+    // or IPv6 unique local addresses, fc00::/7 (RFC 4193).
+    pub fn is_private(&self) -> bool {
+        match *self {
+            IpAddrKind::V4(bits) => bits >> 24 == 10 || bits >> 20 == 0xac1 || bits >> 16 == 0xc0a8,
+            IpAddrKind::V6(bits) => bits >> 121 == 0xfc >> 1,
+        }
+    }
+
+    // 169.254.0.0/16 or fe80::/10.
+    pub fn is_link_local(&self) -> bool {
+        match *self {
+            IpAddrKind::V4(bits) => bits >> 16 == 0xa9fe,
+            IpAddrKind::V6(bits) => bits >> 118 == 0xfe80 >> 6,
+        }
+    }
+
+    // 224.0.0.0/4 or ff00::/8.
+    pub fn is_multicast(&self) -> bool {
+        match *self {
+            IpAddrKind::V4(bits) => bits >> 28 == 0xe,
+            IpAddrKind::V6(bits) => bits >> 120 == 0xff,
+        }
+    }
+}
+
+// Text with a ':' is read as IPv6, anything else as IPv4.
+impl FromStr for IpAddrKind {
+    type Err = IpParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        if text.contains(':') {
+            IpAddrKind::parse_v6(text)
+        } else {
+            IpAddrKind::parse_v4(text)
+        }
+    }
+}
+
+fn write_v4(f: &mut fmt::Formatter, bits: u32) -> fmt::Result {
+    let [a, b, c, d] = bits.to_be_bytes();
+    write!(f, "{a}.{b}.{c}.{d}")
+}
+
+impl fmt::Display for IpAddrKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bits = match *self {
+            IpAddrKind::V4(bits) => return write_v4(f, bits),
+            IpAddrKind::V6(bits) => bits,
+        };
+        // IPv4 addresses mapped into IPv6 keep their dotted form: ::ffff:192.0.2.1.
+        if bits >> 32 == 0xffff {
+            write!(f, "::ffff:")?;
+            return write_v4(f, bits as u32);
+        }
+
+        let segments: Vec<u16> = (0..8).rev().map(|i| (bits >> (i * 16)) as u16).collect();
+        // The longest run of two or more zero groups, the first one on a tie.
+        let mut longest = (0, 0);
+        let mut start = 0;
+        while start < 8 {
+            let length = segments[start..].iter().take_while(|&&s| s == 0).count();
+            if length > longest.1 {
+                longest = (start, length);
+            }
+            start += length.max(1);
+        }
+        let write_groups = |f: &mut fmt::Formatter, groups: &[u16]| {
+            let groups: Vec<String> = groups.iter().map(|g| format!("{g:x}")).collect();
+            write!(f, "{}", groups.join(":"))
+        };
+        if longest.1 < 2 {
+            return write_groups(f, &segments);
+        }
+        write_groups(f, &segments[..longest.0])?;
+        write!(f, "::")?;
+        write_groups(f, &segments[longest.0 + longest.1..])
+    }
+}
@@ -0,0 +1,6 @@
+// IP addresses, checked and stored as numbers, live in the library crate
+// so they can be used from the binary and from integration tests.
+pub mod ip;
+
+// Subnets and a longest-prefix routing table.
+pub mod routing;
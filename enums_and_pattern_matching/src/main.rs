@@ -4,18 +4,23 @@
 // An enum value can either be one of its variants.
 
 // **** Defining an Enum ****
-enum IpAddrKind {
-    V4(String),
-    V6(String),
-}
+// eg enum IpAddrKind {
+//     V4(String),
+//     V6(String),
+// }
 // You can put any data inside of an enum variant.
+// The IpAddrKind used below is in src/ip.rs. It keeps the address as a number,
+// V4(u32) and V6(u128), so only valid addresses can be stored.
+use enums_and_pattern_matching::ip::IpAddrKind;
+use enums_and_pattern_matching::routing::RoutingTable;
 
 // **** The Option Enum and Its Advantages Over Null Values ****
 // It encodes a scenerio where a value could be something or nothing.
-enum Option<T> {
-    Some(T),
-    None,
-}
+// It is in the prelude, defined as:
+// enum Option<T> {
+//     Some(T),
+//     None,
+// }
 
 enum Message {
     Quit,
@@ -33,19 +38,27 @@ enum Coin {
 
 fn main() {
     // **** Enum Values ****
-    let four = IpAddrKind::V4; // They are namespaced under its identifier.
-    let six = IpAddrKind::V6;
+    let _four = IpAddrKind::V4; // They are namespaced under its identifier.
+    let _six = IpAddrKind::V6;
 
     // **** Structs vs Enums ****
     // We can put data directly into each enum variant.
     // Each variant can have different types and amounts of associated data.
     // We can define methods on enums just as we did on structs.
 
-    let home = IpAddrKind::V4(String::from("127.0.0.1"));
-    let loopback = IpAddrKind::V6(String::from("::1"));
+    // Parsing checks the text, so these can fail and return a Result.
+    let home = IpAddrKind::parse_v4("127.0.0.1").unwrap();
+    let loopback = IpAddrKind::parse_v6("::1").unwrap();
 
-    route(IpAddrKind::V4); // We can call a function with any of the variants.
-    route(IpAddrKind::V6);
+    // A routing table says where to send an address; see src/routing.rs.
+    let mut table = RoutingTable::new();
+    table.insert("127.0.0.0/8".parse().unwrap(), "lo");
+    table.insert("::1/128".parse().unwrap(), "lo");
+    table.insert("0.0.0.0/0".parse().unwrap(), "eth0");
+    table.insert("::/0".parse().unwrap(), "eth0");
+
+    route(&table, home); // We can call a function with any of the variants.
+    route(&table, loopback);
 
     // **** The Option Enum ****
     // The Option type encodes the very common scenario in which a value could be something or nothing.
@@ -57,7 +70,12 @@ fn main() {
     // The match control flow operator allows you to compare a value
     // against a series of patterns and then execute code based on which pattern matches.
     // Patterns can be made up of literal values, variable names, wildcards, and many other things.
+    for coin in [Coin::Penny, Coin::Nickel, Coin::Dime, Coin::Quarter] {
+        println!("That coin is worth {} cents", value_in_cents(coin));
+    }
+    println!("One more than 5 is {:?}", plus_one(Some(5)));
 
+    patterns();
 }
 
 fn value_in_cents(coin: Coin) -> u8 {
@@ -75,7 +93,9 @@ fn value_in_cents(coin: Coin) -> u8 {
         Coin::Dime => 10,
         Coin::Quarter => 25,
     }
+}
 
+fn patterns() {
     // **** Patterns and Matching ****
     // The match arms must cover all possibilities.
     // Patterns are a special syntaxt for matching against the structure of types,
@@ -108,14 +128,7 @@ fn value_in_cents(coin: Coin) -> u8 {
     //     pattern => expression,
     //     pattern => expression,
     // }
-    match x {
-        None => None, // None is the pattern and on the where the arrow points to is the expression.
-        Some(i) => Some(i + 1), // Some(i) is the pattern and on the where the arrow points to is the expression.
-        _ => None, // _ is the wildcard pattern.
-        // The underscore, _, is a special pattern that matches any value and does not bind to that value.
-        // This tells Rust we won’t use the value, so Rust won’t warn us about an unused variable.
-        // The underscore pattern will match any value.)
-    }
+    // plus_one below is an example.
     // 2. conditional if let expressions
     // if let matches only one case, and the pattern can only match one value.
     // it can be combined with 'else if', 'else if let' arms, 
@@ -148,14 +161,14 @@ fn value_in_cents(coin: Coin) -> u8 {
         for val in [1, 2, 3] {
             tx.send(val).unwrap();
         }
-    })
+    });
     while let Ok(val) = rx.recv() {
         println!("Got: {val}");
     }
 
     // 4. for loops
     // the value that follows the keyword for is a pattern.
-    let v = vec!['a', 'b', 'c'];
+    let v = ['a', 'b', 'c'];
     for (index, value) in v.iter().enumerate() {
         println!("{} is at index {}", value, index);
     }
@@ -232,41 +245,69 @@ fn value_in_cents(coin: Coin) -> u8 {
 
     // 6. Destructuring Enums
     // Destructuring an enum depends on the way the data is stored within the enum definition.
-    let msg = Message::ChangeColor(0, 160, 255);
-    match msg {
-        Message::Quit => {
-            println!("The Quit variant has no data to destructure.");
-        }
-        Message::Move { x, y } => {
-            println!("Move in the x direction {x} and in the y direction {y}");
-        }
-        Message::Write(text) => println!("Text message: {text}"),
-        Message::ChangeColor(r, g, b) => {
-            println!("Change the color to red {r}, green {g}, and blue {b}",)
+    let messages = [
+        Message::Quit,
+        Message::Move { x: 3, y: -2 },
+        Message::Write(String::from("hello")),
+        Message::ChangeColor(0, 160, 255),
+    ];
+    for msg in messages {
+        match msg {
+            Message::Quit => {
+                println!("The Quit variant has no data to destructure.");
+            }
+            Message::Move { x, y } => {
+                println!("Move in the x direction {x} and in the y direction {y}");
+            }
+            Message::Write(text) => println!("Text message: {text}"),
+            Message::ChangeColor(r, g, b) => {
+                println!("Change the color to red {r}, green {g}, and blue {b}",)
+            }
         }
     }
     // The number of variables in the pattern must match the number of fields in the struct.
 
     // 7. Destructuring Nested Structs and Enums
-    enum Color {
-        Rgb(i32, i32, i32),
-        Hsv(i32, i32, i32),
-    }
-    enum Message {
-        Quit,
-        Move { x: i32, y: i32 },
-        Write(String),
-        ChangeColor(Color),
-    }
-    let msg = Message::ChangeColor(Color::Hsv(0, 160, 255));
-    match msg {
-        Message::ChangeColor(Color::Rgb(r, g, b)) => {
-            println!("Change the color to red {r}, green {g}, and blue {b}",)
+    // These are defined in a block of their own, so that this Message
+    // does not clash with the one above.
+    // The colors are the ones from the structs crate: Color is an RGB tuple
+    // struct and Hsv has named fields, so both kinds of struct pattern show up.
+    {
+        use structs::color::{Color, Hsv};
+
+        // Only the color changes are taken apart below; _ skips the rest.
+        #[allow(dead_code)]
+        enum Message {
+            Quit,
+            Move { x: i32, y: i32 },
+            Write(String),
+            ChangeColor(Color),
+            ChangeHue(Hsv),
         }
-        Message::ChangeColor(Color::Hsv(h, s, v)) => {
-            println!("Change the color to hue {h}, saturation {s}, value {v}")
+        let messages = [
+            Message::ChangeColor(Color(0, 160, 255)),
+            Message::ChangeHue(Color(0, 160, 255).to_hsv()),
+            Message::Quit,
+            Message::Move { x: 3, y: -2 },
+            Message::Write(String::from("hello")),
+        ];
+        for msg in messages {
+            match msg {
+                Message::ChangeColor(Color(r, g, b)) => {
+                    println!("Change the color to red {r}, green {g}, and blue {b}",)
+                }
+                Message::ChangeHue(Hsv {
+                    hue,
+                    saturation,
+                    value,
+                }) => {
+                    println!(
+                        "Change the color to hue {hue:.0}, saturation {saturation:.2}, value {value:.2}"
+                    )
+                }
+                _ => (),
+            }
         }
-        _ => (),
     }
 
     // 8. Destructuring Structs and Tuples
@@ -288,4 +329,28 @@ fn value_in_cents(coin: Coin) -> u8 {
 
 }
 
-fn route(ip_kind: IpAddrKind) {}
+// x.map(|i| i + 1) does the same; the match shows the patterns.
+#[allow(clippy::manual_map)]
+fn plus_one(x: Option<i32>) -> Option<i32> {
+    match x {
+        None => None, // None is the pattern and on the where the arrow points to is the expression.
+        Some(i) => Some(i + 1), // Some(i) is the pattern and on the where the arrow points to is the expression.
+        // eg _ => None, _ is the wildcard pattern.
+        // The underscore, _, is a special pattern that matches any value and does not bind to that value.
+        // This tells Rust we won’t use the value, so Rust won’t warn us about an unused variable.
+        // The underscore pattern will match any value.)
+        // Here None and Some(i) already cover every value, so it is not needed.
+    }
+}
+
+fn route(table: &RoutingTable, ip_kind: IpAddrKind) {
+    match ip_kind {
+        IpAddrKind::V4(_) => println!("{ip_kind} is an IPv4 address"),
+        IpAddrKind::V6(_) => println!("{ip_kind} is an IPv6 address"),
+    }
+    // The most specific route that holds the address wins.
+    match table.lookup(&ip_kind) {
+        Some(route) => println!("{ip_kind} goes to {} via {}", route.next_hop, route.subnet),
+        None => println!("there is no route to {ip_kind}"),
+    }
+}
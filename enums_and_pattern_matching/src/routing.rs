@@ -0,0 +1,396 @@
+// **** Routing ****
+// A Subnet is a network address and a prefix length in CIDR form:
+// 10.0.0.0/8 or 2001:db8::/32. The first `prefix_len` bits are the network,
+// and an address is in the subnet when its first bits are the same.
+//
+// A RoutingTable maps subnets to next hops and finds the route for an
+// address by longest prefix match: of all the subnets that hold the address,
+// the most specific one wins. Each address family has a binary trie with one
+// level per bit, so a lookup follows at most 32 or 128 links however many
+// routes there are. Nodes live in one Vec and point to each other by index,
+// which keeps them small and close together in memory. Removing a route
+// unlinks the nodes that only led to it, and later inserts reuse their slots.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::ip::{IpAddrKind, IpParseError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Subnet {
+    network: IpAddrKind,
+    prefix_len: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubnetError {
+    Address(IpParseError),
+    // There is no "/length" after the address.
+    MissingLength(String),
+    InvalidLength(String),
+    LengthTooLong { length: u8, max: u8 },
+    // An address such as 10.0.0.1/8 has bits set after the prefix; 10.0.0.0/8 was probably meant.
+    HostBitsSet(String),
+}
+
+impl fmt::Display for SubnetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SubnetError::Address(err) => write!(f, "{err}"),
+            SubnetError::MissingLength(text) => {
+                write!(f, "'{text}' needs a prefix length, as in 10.0.0.0/8")
+            }
+            SubnetError::InvalidLength(text) => {
+                write!(f, "'{text}' is not a prefix length")
+            }
+            SubnetError::LengthTooLong { length, max } => {
+                write!(
+                    f,
+                    "a prefix of {length} bits is longer than the {max} bits of the address"
+                )
+            }
+            SubnetError::HostBitsSet(text) => {
+                write!(f, "'{text}' has bits set after the prefix")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SubnetError {}
+
+impl From<IpParseError> for SubnetError {
+    fn from(err: IpParseError) -> Self {
+        SubnetError::Address(err)
+    }
+}
+
+// The address as a u128 with its first bit at the top, and its number of bits.
+// IPv4 addresses are moved to the top so both families can share the trie code.
+fn key(address: &IpAddrKind) -> (u128, u8) {
+    match *address {
+        IpAddrKind::V4(bits) => ((bits as u128) << 96, 32),
+        IpAddrKind::V6(bits) => (bits, 128),
+    }
+}
+
+fn from_key(key: u128, v4: bool) -> IpAddrKind {
+    if v4 {
+        IpAddrKind::V4((key >> 96) as u32)
+    } else {
+        IpAddrKind::V6(key)
+    }
+}
+
+// The top `length` bits set, for a key.
+fn mask(length: u8) -> u128 {
+    if length == 0 {
+        0
+    } else {
+        u128::MAX << (128 - length as u32)
+    }
+}
+
+fn bit(key: u128, index: u8) -> usize {
+    (key >> (127 - index as u32)) as usize & 1
+}
+
+impl Subnet {
+    // The subnet of the given length that holds `address`; the bits after
+    // the prefix are cleared, so 10.1.2.3 with 8 gives 10.0.0.0/8.
+    pub fn new(address: IpAddrKind, prefix_len: u8) -> Result<Subnet, SubnetError> {
+        let (key, max) = key(&address);
+        if prefix_len > max {
+            return Err(SubnetError::LengthTooLong {
+                length: prefix_len,
+                max,
+            });
+        }
+        Ok(Subnet {
+            network: from_key(key & mask(prefix_len), address.is_v4()),
+            prefix_len,
+        })
+    }
+
+    pub fn network(&self) -> IpAddrKind {
+        self.network
+    }
+
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    pub fn contains(&self, address: &IpAddrKind) -> bool {
+        if self.network.is_v4() != address.is_v4() {
+            return false;
+        }
+        let mask = mask(self.prefix_len);
+        key(address).0 & mask == key(&self.network).0
+    }
+}
+
+// Reads address/length. The bits after the prefix must be zero.
+impl FromStr for Subnet {
+    type Err = SubnetError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (address, length) = text
+            .split_once('/')
+            .ok_or_else(|| SubnetError::MissingLength(text.to_string()))?;
+        let address: IpAddrKind = address.parse()?;
+        if length.is_empty() || !length.chars().all(|c| c.is_ascii_digit()) {
+            return Err(SubnetError::InvalidLength(length.to_string()));
+        }
+        let length = length
+            .parse()
+            .map_err(|_| SubnetError::InvalidLength(length.to_string()))?;
+        let subnet = Subnet::new(address, length)?;
+        if subnet.network != address {
+            return Err(SubnetError::HostBitsSet(text.to_string()));
+        }
+        Ok(subnet)
+    }
+}
+
+impl fmt::Display for Subnet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix_len)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub subnet: Subnet,
+    pub next_hop: String,
+}
+
+// Child and route links are indices, with 0 for "none": the root is never
+// anyone's child, and routes are stored one past their index.
+#[derive(Debug, Clone, Copy, Default)]
+struct Node {
+    children: [u32; 2],
+    route: u32,
+}
+
+#[derive(Debug, Clone)]
+struct Trie {
+    nodes: Vec<Node>,
+    // Slots of unlinked nodes, to be used before the Vec grows.
+    free: Vec<u32>,
+}
+
+impl Trie {
+    fn new() -> Trie {
+        Trie {
+            nodes: vec![Node::default()],
+            free: Vec::new(),
+        }
+    }
+
+    fn live_nodes(&self) -> usize {
+        self.nodes.len() - self.free.len()
+    }
+
+    // The node for the prefix, if it has been made.
+    fn find(&self, key: u128, length: u8) -> Option<usize> {
+        let mut node = 0;
+        for index in 0..length {
+            match self.nodes[node].children[bit(key, index)] {
+                0 => return None,
+                child => node = child as usize,
+            }
+        }
+        Some(node)
+    }
+
+    // The node for the prefix, made along with any missing parents.
+    fn find_or_make(&mut self, key: u128, length: u8) -> usize {
+        let mut node = 0;
+        for index in 0..length {
+            let side = bit(key, index);
+            node = match self.nodes[node].children[side] {
+                0 => {
+                    let child = match self.free.pop() {
+                        Some(child) => child,
+                        None => {
+                            self.nodes.push(Node::default());
+                            u32::try_from(self.nodes.len() - 1).expect("fewer than 2^32 trie nodes")
+                        }
+                    };
+                    self.nodes[node].children[side] = child;
+                    child as usize
+                }
+                child => child as usize,
+            };
+        }
+        node
+    }
+
+    // Unlinks the end of the path to the prefix that no longer leads to a
+    // route: every node after the last one that has a route or two children.
+    fn prune(&mut self, key: u128, length: u8) {
+        let mut node = 0;
+        let mut keep = (0, 0);
+        for index in 0..length {
+            let here = self.nodes[node];
+            if here.route != 0 || !here.children.contains(&0) {
+                keep = (node, index);
+            }
+            match here.children[bit(key, index)] {
+                0 => return,
+                child => node = child as usize,
+            }
+        }
+        if node == 0 || self.nodes[node].route != 0 || self.nodes[node].children != [0, 0] {
+            return;
+        }
+
+        let (parent, depth) = keep;
+        let side = bit(key, depth);
+        let mut child = std::mem::take(&mut self.nodes[parent].children[side]);
+        for index in depth + 1..=length {
+            let next = if index < length {
+                self.nodes[child as usize].children[bit(key, index)]
+            } else {
+                0
+            };
+            self.nodes[child as usize] = Node::default();
+            self.free.push(child);
+            child = next;
+        }
+    }
+
+    // The route of the longest prefix on the path to `key`.
+    fn longest_match(&self, key: u128, length: u8) -> Option<usize> {
+        let mut node = 0;
+        let mut best = self.nodes[0].route;
+        for index in 0..length {
+            match self.nodes[node].children[bit(key, index)] {
+                0 => break,
+                child => node = child as usize,
+            }
+            if self.nodes[node].route != 0 {
+                best = self.nodes[node].route;
+            }
+        }
+        best.checked_sub(1).map(|route| route as usize)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RoutingTable {
+    v4: Trie,
+    v6: Trie,
+    routes: Vec<Route>,
+}
+
+impl Default for RoutingTable {
+    fn default() -> Self {
+        RoutingTable::new()
+    }
+}
+
+impl RoutingTable {
+    pub fn new() -> RoutingTable {
+        RoutingTable {
+            v4: Trie::new(),
+            v6: Trie::new(),
+            routes: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.routes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    // Trie nodes in use for both address families, counting the two roots.
+    pub fn node_count(&self) -> usize {
+        self.v4.live_nodes() + self.v6.live_nodes()
+    }
+
+    // In the order they were added, except that removing a route moves the
+    // last one into its place.
+    pub fn routes(&self) -> impl Iterator<Item = &Route> {
+        self.routes.iter()
+    }
+
+    fn trie(&self, subnet: &Subnet) -> &Trie {
+        if subnet.network.is_v4() {
+            &self.v4
+        } else {
+            &self.v6
+        }
+    }
+
+    fn trie_mut(&mut self, subnet: &Subnet) -> &mut Trie {
+        if subnet.network.is_v4() {
+            &mut self.v4
+        } else {
+            &mut self.v6
+        }
+    }
+
+    // Adds a route, or changes the next hop of the subnet's route and
+    // returns the old one.
+    pub fn insert(&mut self, subnet: Subnet, next_hop: impl Into<String>) -> Option<String> {
+        let next_hop = next_hop.into();
+        let (key, _) = key(&subnet.network);
+        let next_route = self.routes.len() + 1;
+        let trie = self.trie_mut(&subnet);
+        let node = trie.find_or_make(key, subnet.prefix_len);
+        match trie.nodes[node].route {
+            0 => {
+                trie.nodes[node].route = u32::try_from(next_route).expect("fewer than 2^32 routes");
+                self.routes.push(Route { subnet, next_hop });
+                None
+            }
+            route => Some(std::mem::replace(
+                &mut self.routes[route as usize - 1].next_hop,
+                next_hop,
+            )),
+        }
+    }
+
+    // Takes out the route of exactly this subnet and returns its next hop.
+    pub fn remove(&mut self, subnet: &Subnet) -> Option<String> {
+        let (key, _) = key(&subnet.network);
+        let trie = self.trie_mut(subnet);
+        let node = trie.find(key, subnet.prefix_len)?;
+        let route = trie.nodes[node].route.checked_sub(1)? as usize;
+        trie.nodes[node].route = 0;
+        trie.prune(key, subnet.prefix_len);
+
+        let removed = self.routes.swap_remove(route);
+        // The last route moved into the gap; point its node at the new place.
+        if let Some(moved) = self.routes.get(route) {
+            let subnet = moved.subnet;
+            let (key, _) = self::key(&subnet.network);
+            let trie = self.trie_mut(&subnet);
+            let node = trie
+                .find(key, subnet.prefix_len)
+                .expect("routes have nodes");
+            trie.nodes[node].route = route as u32 + 1;
+        }
+        Some(removed.next_hop)
+    }
+
+    // The route of exactly this subnet.
+    pub fn get(&self, subnet: &Subnet) -> Option<&Route> {
+        let (key, _) = key(&subnet.network);
+        let trie = self.trie(subnet);
+        let node = trie.find(key, subnet.prefix_len)?;
+        let route = trie.nodes[node].route.checked_sub(1)?;
+        Some(&self.routes[route as usize])
+    }
+
+    // The route of the most specific subnet that holds `address`.
+    pub fn lookup(&self, address: &IpAddrKind) -> Option<&Route> {
+        let (key, length) = key(address);
+        let trie = if address.is_v4() { &self.v4 } else { &self.v6 };
+        trie.longest_match(key, length)
+            .map(|route| &self.routes[route])
+    }
+}
@@ -0,0 +1,154 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use enums_and_pattern_matching::ip::{IpAddrKind, IpParseError};
+
+fn ip(text: &str) -> IpAddrKind {
+    text.parse().unwrap()
+}
+
+#[test]
+fn parses_ipv4_into_numbers() {
+    assert_eq!(ip("127.0.0.1"), IpAddrKind::V4(0x7f00_0001));
+    assert_eq!(ip("0.0.0.0"), IpAddrKind::V4(0));
+    assert_eq!(ip("255.255.255.255"), IpAddrKind::V4(u32::MAX));
+    assert_eq!(
+        IpAddrKind::parse_v4("192.168.0.1"),
+        Ok(IpAddrKind::from_octets([192, 168, 0, 1]))
+    );
+}
+
+#[test]
+fn rejects_bad_ipv4() {
+    for bad in [
+        "",
+        "1.2.3",
+        "1.2.3.4.5",
+        "1.2.3.256",
+        "1.2..4",
+        "01.2.3.4",
+        "1.2.3.-4",
+        "1.2.3.4 ",
+        "a.b.c.d",
+    ] {
+        assert!(
+            matches!(
+                IpAddrKind::parse_v4(bad),
+                Err(IpParseError::InvalidV4 { .. })
+            ),
+            "{bad}"
+        );
+        assert!(bad.parse::<Ipv4Addr>().is_err(), "std accepts {bad}");
+    }
+    assert_eq!(
+        IpAddrKind::parse_v4("1.2.3.256"),
+        Err(IpParseError::InvalidV4 {
+            text: "1.2.3.256".into(),
+            reason: "every number must be between 0 and 255"
+        })
+    );
+}
+
+#[test]
+fn parses_ipv6_with_compression() {
+    assert_eq!(ip("::1"), IpAddrKind::V6(1));
+    assert_eq!(ip("::"), IpAddrKind::V6(0));
+    assert_eq!(
+        ip("2001:db8::8:800:200c:417a"),
+        IpAddrKind::from_segments([0x2001, 0xdb8, 0, 0, 0x8, 0x800, 0x200c, 0x417a])
+    );
+    assert_eq!(ip("fe80::"), IpAddrKind::V6(0xfe80 << 112));
+    assert_eq!(ip("::ffff:192.0.2.1"), IpAddrKind::V6(0xffff_c000_0201));
+    assert_eq!(ip("1:2:3:4:5:6:7:8"), ip("1:2:3:4:5:6:0.7.0.8"));
+}
+
+#[test]
+fn rejects_bad_ipv6() {
+    for bad in [
+        ":",
+        ":::",
+        "1::2::3",
+        ":1:2:3:4:5:6:7",
+        "1:2:3:4:5:6:7",
+        "1:2:3:4:5:6:7:8:9",
+        "1:2:3:4::5:6:7:8",
+        "12345::",
+        "g::",
+        "1.2.3.4::",
+        "::1.2.3",
+        "::1.2.3.4:5",
+    ] {
+        assert!(
+            matches!(
+                IpAddrKind::parse_v6(bad),
+                Err(IpParseError::InvalidV6 { .. })
+            ),
+            "{bad}"
+        );
+        assert!(bad.parse::<Ipv6Addr>().is_err(), "std accepts {bad}");
+    }
+}
+
+#[test]
+fn prints_canonical_form() {
+    for (text, canonical) in [
+        ("127.0.0.1", "127.0.0.1"),
+        ("0:0:0:0:0:0:0:1", "::1"),
+        ("2001:0DB8:0000:0000:0000:0000:0002:0001", "2001:db8::2:1"),
+        ("2001:db8:0:1:1:1:1:1", "2001:db8:0:1:1:1:1:1"),
+        ("2001:0:0:1:0:0:0:1", "2001:0:0:1::1"),
+        ("2001:db8:0:0:1:0:0:1", "2001:db8::1:0:0:1"),
+        ("::ffff:c000:201", "::ffff:192.0.2.1"),
+        ("1::", "1::"),
+    ] {
+        assert_eq!(ip(text).to_string(), canonical, "{text}");
+    }
+}
+
+#[test]
+fn formatting_matches_the_standard_library() {
+    let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed
+    };
+    for _ in 0..2000 {
+        // Zero out random groups so that runs of zeros are common.
+        let mask = next();
+        let segments: [u16; 8] =
+            std::array::from_fn(|i| if mask >> i & 1 == 1 { 0 } else { next() as u16 });
+        let ours = IpAddrKind::from_segments(segments).to_string();
+        assert_eq!(ours, Ipv6Addr::from(segments).to_string());
+        assert_eq!(ip(&ours), IpAddrKind::from_segments(segments));
+    }
+}
+
+#[test]
+fn classifies_addresses() {
+    let home = ip("127.0.0.1");
+    let loopback = ip("::1");
+    assert!(home.is_v4() && home.is_loopback() && !home.is_private());
+    assert!(loopback.is_v6() && loopback.is_loopback());
+    assert!(ip("::").is_unspecified() && ip("0.0.0.0").is_unspecified());
+
+    for private in [
+        "10.1.2.3",
+        "172.16.0.1",
+        "172.31.255.255",
+        "192.168.1.1",
+        "fd12::1",
+        "fc00::",
+    ] {
+        assert!(ip(private).is_private(), "{private}");
+    }
+    for public in ["172.32.0.1", "11.0.0.1", "8.8.8.8", "2001:db8::1", "fe00::"] {
+        assert!(!ip(public).is_private(), "{public}");
+    }
+    assert!(ip("169.254.10.1").is_link_local());
+    assert!(ip("fe80::1").is_link_local() && ip("febf::1").is_link_local());
+    assert!(!ip("fec0::1").is_link_local());
+    assert!(ip("224.0.0.1").is_multicast() && ip("239.255.255.255").is_multicast());
+    assert!(!ip("240.0.0.1").is_multicast());
+    assert!(ip("ff02::1").is_multicast() && !ip("fe02::1").is_multicast());
+}
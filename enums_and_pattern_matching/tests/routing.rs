@@ -0,0 +1,185 @@
+use enums_and_pattern_matching::ip::{IpAddrKind, IpParseError};
+use enums_and_pattern_matching::routing::{RoutingTable, Subnet, SubnetError};
+
+fn ip(text: &str) -> IpAddrKind {
+    text.parse().unwrap()
+}
+
+fn subnet(text: &str) -> Subnet {
+    text.parse().unwrap()
+}
+
+fn next_hop<'a>(table: &'a RoutingTable, address: &str) -> Option<&'a str> {
+    table
+        .lookup(&ip(address))
+        .map(|route| route.next_hop.as_str())
+}
+
+#[test]
+fn parses_cidr_for_both_families() {
+    let ten = subnet("10.0.0.0/8");
+    assert_eq!(ten.network(), ip("10.0.0.0"));
+    assert_eq!(ten.prefix_len(), 8);
+    assert_eq!(ten.to_string(), "10.0.0.0/8");
+    assert_eq!(subnet("2001:DB8::/32").to_string(), "2001:db8::/32");
+    assert_eq!(subnet("0.0.0.0/0").to_string(), "0.0.0.0/0");
+    assert_eq!(subnet("::1/128").network(), ip("::1"));
+
+    assert!(ten.contains(&ip("10.255.0.1")));
+    assert!(!ten.contains(&ip("11.0.0.1")));
+    assert!(!ten.contains(&ip("::a00:1")));
+    assert!(subnet("::/0").contains(&ip("ffff::1")));
+}
+
+#[test]
+fn rejects_bad_cidr() {
+    assert_eq!(
+        "10.0.0.0".parse::<Subnet>(),
+        Err(SubnetError::MissingLength("10.0.0.0".into()))
+    );
+    assert_eq!(
+        "10.0.0.0/x".parse::<Subnet>(),
+        Err(SubnetError::InvalidLength("x".into()))
+    );
+    assert_eq!(
+        "10.0.0.0/+8".parse::<Subnet>(),
+        Err(SubnetError::InvalidLength("+8".into()))
+    );
+    assert_eq!(
+        "10.0.0.0/33".parse::<Subnet>(),
+        Err(SubnetError::LengthTooLong {
+            length: 33,
+            max: 32
+        })
+    );
+    assert_eq!(
+        "::/129".parse::<Subnet>(),
+        Err(SubnetError::LengthTooLong {
+            length: 129,
+            max: 128
+        })
+    );
+    assert_eq!(
+        "10.0.0.1/8".parse::<Subnet>(),
+        Err(SubnetError::HostBitsSet("10.0.0.1/8".into()))
+    );
+    assert!(matches!(
+        "10.0.0/8".parse::<Subnet>(),
+        Err(SubnetError::Address(IpParseError::InvalidV4 { .. }))
+    ));
+    // Subnet::new keeps only the network part.
+    assert_eq!(Subnet::new(ip("10.1.2.3"), 8), Ok(subnet("10.0.0.0/8")));
+}
+
+#[test]
+fn longest_prefix_wins() {
+    let mut table = RoutingTable::new();
+    assert_eq!(next_hop(&table, "10.1.2.3"), None);
+    table.insert(subnet("0.0.0.0/0"), "default");
+    table.insert(subnet("10.0.0.0/8"), "corp");
+    table.insert(subnet("10.1.0.0/16"), "lab");
+    table.insert(subnet("10.1.2.3/32"), "printer");
+    table.insert(subnet("::/0"), "default6");
+    table.insert(subnet("2001:db8::/32"), "docs");
+    assert_eq!(table.len(), 6);
+
+    assert_eq!(next_hop(&table, "10.1.2.3"), Some("printer"));
+    assert_eq!(next_hop(&table, "10.1.2.4"), Some("lab"));
+    assert_eq!(next_hop(&table, "10.2.0.1"), Some("corp"));
+    assert_eq!(next_hop(&table, "192.168.0.1"), Some("default"));
+    assert_eq!(next_hop(&table, "2001:db8::1"), Some("docs"));
+    assert_eq!(next_hop(&table, "2001:db9::1"), Some("default6"));
+    // IPv4 routes never match IPv6 addresses, even when the bits line up.
+    table.remove(&subnet("::/0"));
+    assert_eq!(next_hop(&table, "::a01:203"), None);
+
+    let route = table.lookup(&ip("10.1.9.9")).unwrap();
+    assert_eq!(route.subnet, subnet("10.1.0.0/16"));
+}
+
+#[test]
+fn insert_replaces_and_remove_takes_out() {
+    let mut table = RoutingTable::new();
+    assert_eq!(table.insert(subnet("10.0.0.0/8"), "a"), None);
+    assert_eq!(table.insert(subnet("10.0.0.0/16"), "b"), None);
+    assert_eq!(table.insert(subnet("10.0.0.0/8"), "c"), Some("a".into()));
+    assert_eq!(table.len(), 2);
+    assert_eq!(table.get(&subnet("10.0.0.0/8")).unwrap().next_hop, "c");
+    assert_eq!(table.get(&subnet("10.0.0.0/12")), None);
+
+    assert_eq!(table.remove(&subnet("10.0.0.0/8")), Some("c".into()));
+    assert_eq!(table.remove(&subnet("10.0.0.0/8")), None);
+    assert_eq!(table.remove(&subnet("10.0.0.0/12")), None);
+    assert_eq!(table.len(), 1);
+    // The route that was moved into the freed place can still be found.
+    assert_eq!(next_hop(&table, "10.0.1.1"), Some("b"));
+    assert_eq!(next_hop(&table, "10.1.0.1"), None);
+    table.remove(&subnet("10.0.0.0/16"));
+    assert!(table.is_empty());
+    // Only the two roots are left, and adding a route back reuses the nodes.
+    assert_eq!(table.node_count(), 2);
+    table.insert(subnet("10.0.0.0/16"), "d");
+    table.insert(subnet("192.168.0.0/24"), "e");
+    let nodes = table.node_count();
+    table.remove(&subnet("10.0.0.0/16"));
+    assert_eq!(table.node_count(), nodes - 16);
+    assert_eq!(next_hop(&table, "192.168.0.7"), Some("e"));
+}
+
+#[test]
+fn agrees_with_a_linear_scan() {
+    let mut seed = 0x2545_f491_4f6c_dd1du64;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed
+    };
+    let mut table = RoutingTable::new();
+    let mut routes: Vec<(Subnet, String)> = Vec::new();
+    for index in 0..2000 {
+        // Few distinct top bits, so that prefixes nest often.
+        let address = if index % 2 == 0 {
+            IpAddrKind::V4((next() as u32) & 0xf0ff_ffff)
+        } else {
+            IpAddrKind::V6(((next() as u128) << 64 | next() as u128) & !(0xfff << 116))
+        };
+        let max = if address.is_v4() { 32 } else { 128 };
+        let subnet = Subnet::new(address, (next() % (max + 1)) as u8).unwrap();
+        let hop = format!("hop{index}");
+        table.insert(subnet, hop.clone());
+        routes.retain(|(existing, _)| *existing != subnet);
+        routes.push((subnet, hop));
+    }
+    // Remove a third of them again.
+    for index in (0..routes.len()).rev().step_by(3) {
+        let (subnet, hop) = routes.remove(index);
+        assert_eq!(table.remove(&subnet), Some(hop));
+    }
+    assert_eq!(table.len(), routes.len());
+
+    for _ in 0..5000 {
+        let address = if next() % 2 == 0 {
+            IpAddrKind::V4((next() as u32) & 0xf0ff_ffff)
+        } else {
+            IpAddrKind::V6(((next() as u128) << 64 | next() as u128) & !(0xfff << 116))
+        };
+        let expected = routes
+            .iter()
+            .filter(|(subnet, _)| subnet.contains(&address))
+            .max_by_key(|(subnet, _)| subnet.prefix_len())
+            .map(|(_, hop)| hop.as_str());
+        assert_eq!(
+            table.lookup(&address).map(|route| route.next_hop.as_str()),
+            expected,
+            "{address}"
+        );
+    }
+
+    // Taking out the rest prunes the tries back to their roots.
+    for (subnet, hop) in routes {
+        assert_eq!(table.remove(&subnet), Some(hop));
+    }
+    assert!(table.is_empty());
+    assert_eq!(table.node_count(), 2);
+}
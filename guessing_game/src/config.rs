@@ -0,0 +1,250 @@
+// **** Game Configuration ****
+// The settings for a round are read from the command line.
+// A difficulty preset picks the range and the attempt limit,
+// and --min, --max and --attempts override single values of the preset.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+pub const DEFAULT_SCORE_FILE: &str = "guessing_game_scores.txt";
+pub const DEFAULT_TOP: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn parse(name: &str) -> Option<Difficulty> {
+        match name.to_ascii_lowercase().as_str() {
+            "easy" => Some(Difficulty::Easy),
+            "normal" => Some(Difficulty::Normal),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    // (lower, upper, attempts) used when no override is given.
+    pub fn preset(self) -> (u32, u32, u32) {
+        match self {
+            Difficulty::Easy => (1, 50, 10),
+            Difficulty::Normal => (1, 100, 7),
+            Difficulty::Hard => (1, 1000, 10),
+        }
+    }
+}
+
+// What the program does once the options are parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    // The human guesses the computer's secret.
+    Play,
+    // Print the high-score table.
+    Scores,
+    // The computer guesses the human's secret.
+    Solver,
+    // The computer solves this many of its own secrets and reports the averages.
+    AutoPlay { rounds: u32 },
+    // Pick the secret and wait for a player on this localhost port.
+    Host { port: u16 },
+    // Connect to a hosted game and guess.
+    Join { address: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub lower: u32,
+    pub upper: u32,
+    pub max_attempts: u32,
+    // Seed for the random number generator. Sessions started with the
+    // same seed and range pick the same secret number.
+    pub seed: Option<u64>,
+    pub player: String,
+    pub score_file: PathBuf,
+    pub mode: Mode,
+    pub top: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config::from_difficulty(Difficulty::Normal)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    MissingValue(String),
+    InvalidNumber { option: String, value: String },
+    UnknownDifficulty(String),
+    UnknownOption(String),
+    InvalidRange { lower: u32, upper: u32 },
+    ZeroAttempts,
+    ZeroRounds,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::MissingValue(option) => write!(f, "missing value for {option}"),
+            ConfigError::InvalidNumber { option, value } => {
+                write!(f, "invalid number '{value}' for {option}")
+            }
+            ConfigError::UnknownDifficulty(name) => {
+                write!(
+                    f,
+                    "unknown difficulty '{name}' (expected easy, normal or hard)"
+                )
+            }
+            ConfigError::UnknownOption(option) => write!(f, "unknown option '{option}'"),
+            ConfigError::InvalidRange { lower, upper } => {
+                write!(
+                    f,
+                    "invalid range: lower bound {lower} must be less than upper bound {upper}"
+                )
+            }
+            ConfigError::ZeroAttempts => write!(f, "the number of attempts must be at least 1"),
+            ConfigError::ZeroRounds => {
+                write!(f, "the number of auto-play rounds must be at least 1")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+pub const USAGE: &str = "\
+Usage: guessing_game [OPTIONS]
+
+Options:
+    --difficulty <easy|normal|hard>  Pick a preset range and attempt limit (default: normal)
+    --min <N>                        Lower bound of the secret number
+    --max <N>                        Upper bound of the secret number
+    --attempts <N>                   Maximum number of guesses
+    --seed <N>                       Seed the random generator for a reproducible session
+    --name <NAME>                    Player name recorded in the high-score table
+    --score-file <PATH>              High-score file (default: guessing_game_scores.txt)
+    --scores                         Print the high-score table and exit
+    --top <N>                        Number of entries shown by --scores (default: 10)
+    --solver                         Think of a number and let the computer guess it
+    --auto-play <ROUNDS>             Let the solver play ROUNDS games and report the average guesses
+    --host <PORT>                    Pick a secret and wait for a second player on localhost:PORT
+    --join <ADDRESS>                 Guess the secret of a hosted game, e.g. 127.0.0.1:7878
+    --help                           Print this message";
+
+impl Config {
+    pub fn from_difficulty(difficulty: Difficulty) -> Config {
+        let (lower, upper, max_attempts) = difficulty.preset();
+        Config {
+            lower,
+            upper,
+            max_attempts,
+            seed: None,
+            player: String::from("anonymous"),
+            score_file: PathBuf::from(DEFAULT_SCORE_FILE),
+            mode: Mode::Play,
+            top: DEFAULT_TOP,
+        }
+    }
+
+    // Parses the arguments that follow the program name.
+    pub fn from_args<I>(args: I) -> Result<Config, ConfigError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut difficulty = Difficulty::Normal;
+        let mut lower = None;
+        let mut upper = None;
+        let mut attempts = None;
+        let mut seed = None;
+        let mut settings = Config::default();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--difficulty" => {
+                    let value = next_value(&mut args, &arg)?;
+                    difficulty =
+                        Difficulty::parse(&value).ok_or(ConfigError::UnknownDifficulty(value))?;
+                }
+                "--min" => lower = Some(parse_number(&mut args, &arg)?),
+                "--max" => upper = Some(parse_number(&mut args, &arg)?),
+                "--attempts" => attempts = Some(parse_number(&mut args, &arg)?),
+                "--seed" => seed = Some(parse_number(&mut args, &arg)?),
+                "--name" => settings.player = next_value(&mut args, &arg)?,
+                "--score-file" => settings.score_file = PathBuf::from(next_value(&mut args, &arg)?),
+                "--scores" => settings.mode = Mode::Scores,
+                "--solver" => settings.mode = Mode::Solver,
+                "--host" => {
+                    settings.mode = Mode::Host {
+                        port: parse_number(&mut args, &arg)?,
+                    }
+                }
+                "--join" => {
+                    settings.mode = Mode::Join {
+                        address: next_value(&mut args, &arg)?,
+                    }
+                }
+                "--auto-play" => {
+                    settings.mode = Mode::AutoPlay {
+                        rounds: parse_number(&mut args, &arg)?,
+                    }
+                }
+                "--top" => settings.top = parse_number(&mut args, &arg)?,
+                _ => return Err(ConfigError::UnknownOption(arg)),
+            }
+        }
+
+        let (preset_lower, preset_upper, preset_attempts) = difficulty.preset();
+        let config = Config {
+            lower: lower.unwrap_or(preset_lower),
+            upper: upper.unwrap_or(preset_upper),
+            max_attempts: attempts.unwrap_or(preset_attempts),
+            seed,
+            ..settings
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.lower >= self.upper {
+            return Err(ConfigError::InvalidRange {
+                lower: self.lower,
+                upper: self.upper,
+            });
+        }
+        if self.max_attempts == 0 {
+            return Err(ConfigError::ZeroAttempts);
+        }
+        if self.mode == (Mode::AutoPlay { rounds: 0 }) {
+            return Err(ConfigError::ZeroRounds);
+        }
+        Ok(())
+    }
+}
+
+fn next_value<I>(args: &mut I, option: &str) -> Result<String, ConfigError>
+where
+    I: Iterator<Item = String>,
+{
+    args.next()
+        .ok_or_else(|| ConfigError::MissingValue(option.to_string()))
+}
+
+fn parse_number<I, T>(args: &mut I, option: &str) -> Result<T, ConfigError>
+where
+    I: Iterator<Item = String>,
+    T: FromStr,
+{
+    let value = next_value(args, option)?;
+    value
+        .trim()
+        .parse()
+        .map_err(|_| ConfigError::InvalidNumber {
+            option: option.to_string(),
+            value,
+        })
+}
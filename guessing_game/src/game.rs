@@ -0,0 +1,161 @@
+// **** The Game Loop ****
+// The loop reads guesses from any BufRead and writes the transcript to any Write,
+// so a whole game can be driven from a test with scripted input.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::config::Config;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Won { attempts: u32 },
+    Lost { secret: u32 },
+    // The input ended before the game was decided.
+    Abandoned,
+}
+
+// Why a line of input was not accepted as a guess.
+// Rejected lines do not use up an attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessError {
+    NotANumber(String),
+    Negative(i64),
+    OutOfRange { guess: i64, lower: u32, upper: u32 },
+    Duplicate(u32),
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GuessError::NotANumber(input) => write!(f, "'{input}' is not a number."),
+            GuessError::Negative(guess) => write!(
+                f,
+                "{guess} is negative; the secret number is never below zero."
+            ),
+            GuessError::OutOfRange {
+                guess,
+                lower,
+                upper,
+            } => {
+                write!(
+                    f,
+                    "{guess} is out of range; guess between {lower} and {upper}."
+                )
+            }
+            GuessError::Duplicate(guess) => write!(f, "You already guessed {guess}."),
+        }
+    }
+}
+
+pub fn check_guess(input: &str, config: &Config, history: &[u32]) -> Result<u32, GuessError> {
+    let input = input.trim();
+    let guess: i64 = input
+        .parse()
+        .map_err(|_| GuessError::NotANumber(input.to_string()))?;
+    if guess < 0 {
+        return Err(GuessError::Negative(guess));
+    }
+    let in_range = u32::try_from(guess)
+        .ok()
+        .filter(|guess| (config.lower..=config.upper).contains(guess));
+    let Some(guess) = in_range else {
+        return Err(GuessError::OutOfRange {
+            guess,
+            lower: config.lower,
+            upper: config.upper,
+        });
+    };
+    if history.contains(&guess) {
+        return Err(GuessError::Duplicate(guess));
+    }
+    Ok(guess)
+}
+
+// A seeded config gives a reproducible generator, otherwise it is seeded from the OS.
+pub fn make_rng(config: &Config) -> StdRng {
+    match config.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+pub fn secret_number<R: Rng>(config: &Config, rng: &mut R) -> u32 {
+    rng.gen_range(config.lower..=config.upper)
+}
+
+pub fn play<R, W>(
+    config: &Config,
+    secret_number: u32,
+    mut input: R,
+    mut output: W,
+) -> io::Result<Outcome>
+where
+    R: BufRead,
+    W: Write,
+{
+    writeln!(output, "Guess the number!")?;
+    writeln!(
+        output,
+        "The secret number is between {} and {}. You have {} attempts.",
+        config.lower, config.upper, config.max_attempts
+    )?;
+
+    let mut history: Vec<u32> = Vec::new();
+    // The interval the secret is known to be in, narrowed after every guess.
+    let (mut low, mut high) = (config.lower, config.upper);
+
+    let mut attempts = 0;
+    while attempts < config.max_attempts {
+        writeln!(output, "Please input your guess.")?;
+
+        let mut guess = String::new();
+        if input.read_line(&mut guess)? == 0 {
+            return Ok(Outcome::Abandoned);
+        }
+
+        let guess = match check_guess(&guess, config, &history) {
+            Ok(num) => num,
+            Err(err) => {
+                writeln!(output, "{err}")?;
+                continue;
+            }
+        };
+
+        attempts += 1;
+        history.push(guess);
+        writeln!(output, "You guessed: {}", guess)?;
+
+        match guess.cmp(&secret_number) {
+            Ordering::Less => {
+                writeln!(output, "Too small!")?;
+                low = low.max(guess + 1);
+            }
+            Ordering::Greater => {
+                writeln!(output, "Too big!")?;
+                high = high.min(guess - 1);
+            }
+            Ordering::Equal => {
+                writeln!(output, "You win!")?;
+                return Ok(Outcome::Won { attempts });
+            }
+        }
+
+        let previous: Vec<String> = history.iter().map(|guess| guess.to_string()).collect();
+        writeln!(output, "Previous guesses: {}", previous.join(", "))?;
+        writeln!(output, "The number is between {} and {}.", low, high)?;
+    }
+
+    writeln!(
+        output,
+        "You lose! You ran out of attempts. The secret number was {}.",
+        secret_number
+    )?;
+    Ok(Outcome::Lost {
+        secret: secret_number,
+    })
+}
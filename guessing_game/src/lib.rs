@@ -0,0 +1,7 @@
+// The game logic lives in the library crate so it can be reused
+// by the binary and driven from integration tests.
+pub mod config;
+pub mod game;
+pub mod net;
+pub mod scores;
+pub mod solver;
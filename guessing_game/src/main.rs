@@ -1,36 +1,154 @@
 use std::io;
-use rand::Rng;
-use std::cmp::Ordering;
+use std::net::TcpListener;
+use std::process;
+use std::time::Instant;
+
+use guessing_game::config::{self, Config, Mode};
+use guessing_game::game::{self, Outcome};
+use guessing_game::net;
+use guessing_game::scores::{ScoreEntry, ScoreTable};
+use guessing_game::solver::{self, SolverOutcome};
 
 fn main() {
-    println!("Guess the number!");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", config::USAGE);
+        return;
+    }
+
+    let config = match Config::from_args(args) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("error: {err}");
+            eprintln!("{}", config::USAGE);
+            process::exit(2);
+        }
+    };
+
+    match &config.mode {
+        Mode::Play => play(&config),
+        Mode::Scores => show_scores(&config),
+        Mode::Solver => run_solver(&config),
+        Mode::AutoPlay { rounds } => auto_play(&config, *rounds),
+        Mode::Host { port } => host(&config, *port),
+        Mode::Join { address } => join(address),
+    }
+}
+
+fn load_scores(config: &Config) -> ScoreTable {
+    // A corrupt or unreadable score file should never stop a game.
+    ScoreTable::load(&config.score_file).unwrap_or_else(|err| {
+        eprintln!("warning: {err}; starting a fresh high-score table");
+        ScoreTable::default()
+    })
+}
+
+fn show_scores(config: &Config) {
+    if let Err(err) = load_scores(config).print_top(config.top, io::stdout()) {
+        eprintln!("error: {err}");
+        process::exit(1);
+    }
+}
+
+fn play(config: &Config) {
+    let mut table = load_scores(config);
 
-    let secret_number = rand::thread_rng().gen_range(1..=100);
+    let mut rng = game::make_rng(config);
+    let secret_number = game::secret_number(config, &mut rng);
 
-    
-    loop {
-        println!("Please input your guess.");
+    let started = Instant::now();
+    let stdin = io::stdin();
+    let outcome = match game::play(config, secret_number, stdin.lock(), io::stdout()) {
+        Ok(outcome) => outcome,
+        Err(err) => {
+            eprintln!("error: {err}");
+            process::exit(1);
+        }
+    };
 
-        let mut guess = String::new();
+    let (guesses, won) = match outcome {
+        Outcome::Won { attempts } => (attempts, true),
+        Outcome::Lost { .. } => (config.max_attempts, false),
+        Outcome::Abandoned => return,
+    };
+    table.record(ScoreEntry::new(
+        &config.player,
+        config.lower,
+        config.upper,
+        guesses,
+        won,
+        started.elapsed(),
+    ));
+    if let Err(err) = table.save(&config.score_file) {
+        eprintln!("warning: the game was not recorded: {err}");
+    }
+}
 
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
+fn run_solver(config: &Config) {
+    let stdin = io::stdin();
+    match solver::solve(config, stdin.lock(), io::stdout()) {
+        Ok(SolverOutcome::Inconsistent(_)) => process::exit(1),
+        Ok(_) => {}
+        Err(err) => {
+            eprintln!("error: {err}");
+            process::exit(1);
+        }
+    }
+}
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
-        };
+fn auto_play(config: &Config, rounds: u32) {
+    let mut rng = game::make_rng(config);
+    let result = solver::benchmark(config, rounds, &mut rng);
+    println!(
+        "Solved {} games between {} and {}.",
+        result.rounds, config.lower, config.upper
+    );
+    println!(
+        "Average guesses: {:.2} (fewest {}, most {})",
+        result.average(),
+        result.fewest,
+        result.most
+    );
+}
 
-        println!("You guessed: {}", guess);
+fn host(config: &Config, port: u16) {
+    let listener = match TcpListener::bind(("127.0.0.1", port)) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("error: could not listen on port {port}: {err}");
+            process::exit(1);
+        }
+    };
+    println!("Waiting for a player on 127.0.0.1:{port}...");
+
+    let mut rng = game::make_rng(config);
+    let secret_number = game::secret_number(config, &mut rng);
+    match net::host(
+        config,
+        secret_number,
+        &listener,
+        net::READ_TIMEOUT,
+        io::stdout(),
+    ) {
+        Ok(Outcome::Abandoned) => {
+            println!("The game ended early. The secret number was {secret_number}.")
+        }
+        Ok(_) => {}
+        Err(err) => {
+            eprintln!("error: {err}");
+            process::exit(1);
+        }
+    }
+}
 
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
-                break;
-            }
+fn join(address: &str) {
+    let stdin = io::stdin();
+    match net::join(address, net::READ_TIMEOUT, stdin.lock(), io::stdout()) {
+        Ok(Outcome::Abandoned) => println!("The game ended early."),
+        Ok(_) => {}
+        Err(err) => {
+            eprintln!("error: {err}");
+            process::exit(1);
         }
     }
 }
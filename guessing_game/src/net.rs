@@ -0,0 +1,356 @@
+// **** Two-Player Mode over TCP ****
+// One side hosts on a local port and picks the secret, the other side connects and guesses.
+// Both sides speak a small line based protocol:
+//
+//   host  -> guest   HELLO <lower> <upper> <attempts>
+//   guest -> host    GUESS <n>
+//   host  -> guest   LESS | GREATER | EQUAL      (guess.cmp(&secret))
+//   host  -> guest   LOST <secret>               (after the last wrong guess)
+//   host  -> guest   ERROR <reason>              (the guess was rejected, try again)
+//
+// Either side gives up on a peer that stays silent for longer than the read timeout.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::game::{self, Outcome};
+
+// Longest line either side accepts, so a misbehaving peer cannot exhaust memory.
+const MAX_LINE: u64 = 256;
+
+// How long the command line waits for the other player, who may be thinking.
+pub const READ_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Hello {
+        lower: u32,
+        upper: u32,
+        attempts: u32,
+    },
+    Guess(u32),
+    Reply(Ordering),
+    Lost {
+        secret: u32,
+    },
+    Error(String),
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Message::Hello {
+                lower,
+                upper,
+                attempts,
+            } => write!(f, "HELLO {lower} {upper} {attempts}"),
+            Message::Guess(guess) => write!(f, "GUESS {guess}"),
+            Message::Reply(Ordering::Less) => write!(f, "LESS"),
+            Message::Reply(Ordering::Greater) => write!(f, "GREATER"),
+            Message::Reply(Ordering::Equal) => write!(f, "EQUAL"),
+            Message::Lost { secret } => write!(f, "LOST {secret}"),
+            Message::Error(reason) => write!(f, "ERROR {reason}"),
+        }
+    }
+}
+
+impl Message {
+    pub fn parse(line: &str) -> Result<Message, NetError> {
+        let malformed = || NetError::Malformed(line.trim().to_string());
+        let mut words = line.split_whitespace();
+        let command = words.next().ok_or_else(malformed)?;
+        let mut number = || -> Result<u32, NetError> {
+            words
+                .next()
+                .and_then(|word| word.parse().ok())
+                .ok_or_else(malformed)
+        };
+
+        let message = match command {
+            "HELLO" => Message::Hello {
+                lower: number()?,
+                upper: number()?,
+                attempts: number()?,
+            },
+            "GUESS" => Message::Guess(number()?),
+            "LESS" => Message::Reply(Ordering::Less),
+            "GREATER" => Message::Reply(Ordering::Greater),
+            "EQUAL" => Message::Reply(Ordering::Equal),
+            "LOST" => Message::Lost { secret: number()? },
+            "ERROR" => {
+                let reason = line.trim().strip_prefix("ERROR").unwrap_or_default();
+                return Ok(Message::Error(reason.trim().to_string()));
+            }
+            _ => return Err(malformed()),
+        };
+        if words.next().is_some() {
+            return Err(malformed());
+        }
+        Ok(message)
+    }
+}
+
+#[derive(Debug)]
+pub enum NetError {
+    Io(io::Error),
+    Malformed(String),
+    Unexpected(Message),
+}
+
+impl fmt::Display for NetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NetError::Io(err) => write!(f, "connection error: {err}"),
+            NetError::Malformed(line) => write!(f, "malformed message '{line}'"),
+            NetError::Unexpected(message) => write!(f, "unexpected message '{message}'"),
+        }
+    }
+}
+
+impl std::error::Error for NetError {}
+
+impl From<io::Error> for NetError {
+    fn from(err: io::Error) -> Self {
+        NetError::Io(err)
+    }
+}
+
+// Errors that mean the other side went away or stopped answering,
+// rather than something we did wrong.
+fn is_disconnect(err: &NetError) -> bool {
+    match err {
+        NetError::Io(err) => matches!(
+            err.kind(),
+            io::ErrorKind::BrokenPipe
+                | io::ErrorKind::ConnectionReset
+                | io::ErrorKind::ConnectionAborted
+                | io::ErrorKind::UnexpectedEof
+                | io::ErrorKind::TimedOut
+                | io::ErrorKind::WouldBlock
+        ),
+        _ => false,
+    }
+}
+
+fn send<W: Write>(writer: &mut W, message: &Message) -> Result<(), NetError> {
+    writeln!(writer, "{message}")?;
+    writer.flush()?;
+    Ok(())
+}
+
+// Reads one message; Ok(None) means the peer closed the connection.
+fn receive<R: BufRead>(reader: &mut R) -> Result<Option<Message>, NetError> {
+    let mut line = String::new();
+    match reader.by_ref().take(MAX_LINE).read_line(&mut line) {
+        Ok(0) => Ok(None),
+        Ok(_) => Message::parse(&line).map(Some),
+        Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+            Err(NetError::Malformed(String::from("<invalid utf-8>")))
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+// Accepts a single player on `listener` and plays one game against `secret`.
+// A player who sends nothing for `timeout` has abandoned the game.
+pub fn host<W: Write>(
+    config: &Config,
+    secret: u32,
+    listener: &TcpListener,
+    timeout: Duration,
+    mut log: W,
+) -> Result<Outcome, NetError> {
+    let (stream, peer) = listener.accept()?;
+    stream.set_read_timeout(Some(timeout))?;
+    writeln!(log, "Player connected from {peer}.")?;
+
+    let reader = BufReader::new(stream.try_clone()?);
+    match serve(config, secret, reader, stream, &mut log) {
+        Err(err) if is_disconnect(&err) => Ok(Outcome::Abandoned),
+        result => result,
+    }
+}
+
+fn serve<R, S, W>(
+    config: &Config,
+    secret: u32,
+    mut reader: R,
+    mut stream: S,
+    log: &mut W,
+) -> Result<Outcome, NetError>
+where
+    R: BufRead,
+    S: Write,
+    W: Write,
+{
+    send(
+        &mut stream,
+        &Message::Hello {
+            lower: config.lower,
+            upper: config.upper,
+            attempts: config.max_attempts,
+        },
+    )?;
+
+    let mut history = Vec::new();
+    let mut attempts = 0;
+    while attempts < config.max_attempts {
+        let guess = match receive(&mut reader) {
+            Ok(None) => {
+                writeln!(log, "The player disconnected.")?;
+                return Ok(Outcome::Abandoned);
+            }
+            Ok(Some(Message::Guess(guess))) => guess,
+            Ok(Some(message)) => {
+                send(
+                    &mut stream,
+                    &Message::Error(format!("expected GUESS, got {message}")),
+                )?;
+                continue;
+            }
+            Err(err @ (NetError::Malformed(_) | NetError::Unexpected(_))) => {
+                send(&mut stream, &Message::Error(err.to_string()))?;
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+
+        if let Err(err) = game::check_guess(&guess.to_string(), config, &history) {
+            send(&mut stream, &Message::Error(err.to_string()))?;
+            continue;
+        }
+
+        attempts += 1;
+        history.push(guess);
+        let answer = guess.cmp(&secret);
+        writeln!(log, "The player guessed {guess}: {answer:?}.")?;
+        send(&mut stream, &Message::Reply(answer))?;
+        if answer == Ordering::Equal {
+            writeln!(log, "The player found the secret in {attempts} guesses.")?;
+            return Ok(Outcome::Won { attempts });
+        }
+    }
+
+    send(&mut stream, &Message::Lost { secret })?;
+    writeln!(log, "The player ran out of attempts.")?;
+    Ok(Outcome::Lost { secret })
+}
+
+// Connects to a host and plays with guesses read from `input`.
+// A host that sends nothing for `timeout` has abandoned the game.
+pub fn join<A, R, W>(
+    address: A,
+    timeout: Duration,
+    input: R,
+    output: W,
+) -> Result<Outcome, NetError>
+where
+    A: ToSocketAddrs,
+    R: BufRead,
+    W: Write,
+{
+    let stream = TcpStream::connect(address)?;
+    stream.set_read_timeout(Some(timeout))?;
+    let reader = BufReader::new(stream.try_clone()?);
+    match play_remote(reader, stream, input, output) {
+        Err(err) if is_disconnect(&err) => Ok(Outcome::Abandoned),
+        result => result,
+    }
+}
+
+fn play_remote<N, S, R, W>(
+    mut reader: N,
+    mut stream: S,
+    mut input: R,
+    mut output: W,
+) -> Result<Outcome, NetError>
+where
+    N: BufRead,
+    S: Write,
+    R: BufRead,
+    W: Write,
+{
+    let config = match receive(&mut reader)? {
+        Some(Message::Hello {
+            lower,
+            upper,
+            attempts,
+        }) => Config {
+            lower,
+            upper,
+            max_attempts: attempts,
+            ..Config::default()
+        },
+        Some(message) => return Err(NetError::Unexpected(message)),
+        None => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+    };
+    // A game with an empty range or no attempts could never end.
+    if config.validate().is_err() {
+        let hello = Message::Hello {
+            lower: config.lower,
+            upper: config.upper,
+            attempts: config.max_attempts,
+        };
+        return Err(NetError::Malformed(hello.to_string()));
+    }
+    writeln!(output, "Connected! Guess the number!")?;
+    writeln!(
+        output,
+        "The secret number is between {} and {}. You have {} attempts.",
+        config.lower, config.upper, config.max_attempts
+    )?;
+
+    let mut history = Vec::new();
+    loop {
+        writeln!(output, "Please input your guess.")?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(Outcome::Abandoned);
+        }
+        let guess = match game::check_guess(&line, &config, &history) {
+            Ok(guess) => guess,
+            Err(err) => {
+                writeln!(output, "{err}")?;
+                continue;
+            }
+        };
+
+        send(&mut stream, &Message::Guess(guess))?;
+        let reply = receive(&mut reader)?.ok_or(io::Error::from(io::ErrorKind::UnexpectedEof))?;
+        match reply {
+            Message::Reply(Ordering::Less) => writeln!(output, "Too small!")?,
+            Message::Reply(Ordering::Greater) => writeln!(output, "Too big!")?,
+            Message::Reply(Ordering::Equal) => {
+                writeln!(output, "You win!")?;
+                return Ok(Outcome::Won {
+                    attempts: history.len() as u32 + 1,
+                });
+            }
+            Message::Error(reason) => {
+                writeln!(output, "The host rejected the guess: {reason}")?;
+                continue;
+            }
+            message => return Err(NetError::Unexpected(message)),
+        }
+        history.push(guess);
+
+        if history.len() as u32 == config.max_attempts {
+            return match receive(&mut reader)? {
+                Some(Message::Lost { secret }) => {
+                    writeln!(
+                        output,
+                        "You lose! You ran out of attempts. The secret number was {}.",
+                        secret
+                    )?;
+                    Ok(Outcome::Lost { secret })
+                }
+                Some(message) => Err(NetError::Unexpected(message)),
+                None => Ok(Outcome::Abandoned),
+            };
+        }
+    }
+}
@@ -0,0 +1,195 @@
+// **** High Scores ****
+// Every finished game is appended to a local score file.
+// The file holds one game per line with tab separated fields:
+// player, lower, upper, guesses, won, elapsed milliseconds, finish time (unix seconds).
+
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreEntry {
+    pub player: String,
+    pub lower: u32,
+    pub upper: u32,
+    pub guesses: u32,
+    pub won: bool,
+    pub elapsed: Duration,
+    pub finished_at: u64,
+}
+
+impl ScoreEntry {
+    pub fn new(
+        player: &str,
+        lower: u32,
+        upper: u32,
+        guesses: u32,
+        won: bool,
+        elapsed: Duration,
+    ) -> ScoreEntry {
+        let finished_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_secs())
+            .unwrap_or(0);
+        ScoreEntry {
+            // Tabs and newlines would break the line format.
+            player: player.replace(['\t', '\n', '\r'], " "),
+            lower,
+            upper,
+            guesses,
+            won,
+            elapsed,
+            finished_at,
+        }
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.player,
+            self.lower,
+            self.upper,
+            self.guesses,
+            self.won,
+            self.elapsed.as_millis(),
+            self.finished_at
+        )
+    }
+
+    fn from_line(line: &str) -> Option<ScoreEntry> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 7 {
+            return None;
+        }
+        Some(ScoreEntry {
+            player: fields[0].to_string(),
+            lower: fields[1].parse().ok()?,
+            upper: fields[2].parse().ok()?,
+            guesses: fields[3].parse().ok()?,
+            won: fields[4].parse().ok()?,
+            elapsed: Duration::from_millis(fields[5].parse().ok()?),
+            finished_at: fields[6].parse().ok()?,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum ScoreError {
+    Io(io::Error),
+    Corrupt { line: usize },
+}
+
+impl fmt::Display for ScoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScoreError::Io(err) => write!(f, "could not access the score file: {err}"),
+            ScoreError::Corrupt { line } => write!(f, "the score file is corrupt at line {line}"),
+        }
+    }
+}
+
+impl std::error::Error for ScoreError {}
+
+impl From<io::Error> for ScoreError {
+    fn from(err: io::Error) -> Self {
+        ScoreError::Io(err)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScoreTable {
+    entries: Vec<ScoreEntry>,
+}
+
+impl ScoreTable {
+    // A missing file is an empty table; a file that cannot be parsed is an error
+    // so the caller can decide to start over.
+    pub fn load(path: &Path) -> Result<ScoreTable, ScoreError> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(ScoreTable::default()),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut entries = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry =
+                ScoreEntry::from_line(line).ok_or(ScoreError::Corrupt { line: index + 1 })?;
+            entries.push(entry);
+        }
+        Ok(ScoreTable { entries })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ScoreError> {
+        let mut contents = String::new();
+        for entry in &self.entries {
+            contents.push_str(&entry.to_line());
+            contents.push('\n');
+        }
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, entry: ScoreEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn entries(&self) -> &[ScoreEntry] {
+        &self.entries
+    }
+
+    // Won games ranked by fewest guesses, then fastest time.
+    pub fn top(&self, n: usize) -> Vec<&ScoreEntry> {
+        let mut winners: Vec<&ScoreEntry> = self.entries.iter().filter(|entry| entry.won).collect();
+        winners.sort_by(|a, b| a.guesses.cmp(&b.guesses).then(a.elapsed.cmp(&b.elapsed)));
+        winners.truncate(n);
+        winners
+    }
+
+    pub fn print_top<W: Write>(&self, n: usize, mut output: W) -> io::Result<()> {
+        let top = self.top(n);
+        if top.is_empty() {
+            return writeln!(output, "No high scores yet.");
+        }
+
+        writeln!(
+            output,
+            "{:>4}  {:<16} {:>13} {:>7} {:>9}  {:<10}",
+            "Rank", "Player", "Range", "Guesses", "Time", "Date"
+        )?;
+        for (rank, entry) in top.iter().enumerate() {
+            writeln!(
+                output,
+                "{:>4}  {:<16} {:>13} {:>7} {:>8.1}s  {:<10}",
+                rank + 1,
+                entry.player,
+                format!("{}..={}", entry.lower, entry.upper),
+                entry.guesses,
+                entry.elapsed.as_secs_f64(),
+                format_date(entry.finished_at)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// Formats unix seconds as a YYYY-MM-DD date (UTC).
+pub fn format_date(unix_secs: u64) -> String {
+    // Days to civil date conversion from Howard Hinnant's date algorithms.
+    let days = (unix_secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{year:04}-{month:02}-{day:02}")
+}
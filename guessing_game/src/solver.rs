@@ -0,0 +1,189 @@
+// **** The Solver ****
+// The roles are reversed: the human picks the number and the computer guesses.
+// Every reply is turned into the same Ordering the game uses, guess.cmp(&secret),
+// and the solver keeps halving the interval the secret can still be in.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use rand::Rng;
+
+use crate::config::Config;
+use crate::game;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solver {
+    low: u32,
+    high: u32,
+    guesses: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverError {
+    // The replies so far rule out every number in the range.
+    Inconsistent { guess: u32, answer: Ordering },
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolverError::Inconsistent { guess, answer } => {
+                let reply = match answer {
+                    Ordering::Less => "higher",
+                    Ordering::Greater => "lower",
+                    Ordering::Equal => "correct",
+                };
+                write!(
+                    f,
+                    "saying '{reply}' to {guess} contradicts your earlier answers"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolverError {}
+
+impl Solver {
+    pub fn new(lower: u32, upper: u32) -> Solver {
+        Solver {
+            low: lower,
+            high: upper,
+            guesses: 0,
+        }
+    }
+
+    // The middle of the remaining interval.
+    pub fn next_guess(&self) -> u32 {
+        self.low + (self.high - self.low) / 2
+    }
+
+    pub fn guesses(&self) -> u32 {
+        self.guesses
+    }
+
+    // `answer` is guess.cmp(&secret): Less means the secret is higher.
+    pub fn feedback(&mut self, guess: u32, answer: Ordering) -> Result<(), SolverError> {
+        let inconsistent = SolverError::Inconsistent { guess, answer };
+        if guess < self.low || guess > self.high {
+            return Err(inconsistent);
+        }
+        match answer {
+            Ordering::Less if guess < self.high => self.low = guess + 1,
+            Ordering::Greater if guess > self.low => self.high = guess - 1,
+            Ordering::Equal => {}
+            _ => return Err(inconsistent),
+        }
+        self.guesses += 1;
+        Ok(())
+    }
+}
+
+// Reads a human reply. "higher" means the secret is higher than the guess.
+pub fn parse_reply(reply: &str) -> Option<Ordering> {
+    match reply.trim().to_ascii_lowercase().as_str() {
+        "higher" | "h" | "+" => Some(Ordering::Less),
+        "lower" | "l" | "-" => Some(Ordering::Greater),
+        "correct" | "c" | "=" => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverOutcome {
+    Solved { secret: u32, guesses: u32 },
+    Inconsistent(SolverError),
+    Abandoned,
+}
+
+pub fn solve<R, W>(config: &Config, mut input: R, mut output: W) -> io::Result<SolverOutcome>
+where
+    R: BufRead,
+    W: Write,
+{
+    writeln!(
+        output,
+        "Think of a number between {} and {}. I will guess it.",
+        config.lower, config.upper
+    )?;
+
+    let mut solver = Solver::new(config.lower, config.upper);
+    loop {
+        let guess = solver.next_guess();
+        writeln!(
+            output,
+            "My guess is {guess}. Is your number higher, lower, or correct?"
+        )?;
+
+        let mut reply = String::new();
+        if input.read_line(&mut reply)? == 0 {
+            return Ok(SolverOutcome::Abandoned);
+        }
+
+        let Some(answer) = parse_reply(&reply) else {
+            writeln!(output, "Please answer 'higher', 'lower' or 'correct'.")?;
+            continue;
+        };
+
+        if let Err(err) = solver.feedback(guess, answer) {
+            writeln!(output, "You are cheating: {err}.")?;
+            return Ok(SolverOutcome::Inconsistent(err));
+        }
+
+        if answer == Ordering::Equal {
+            writeln!(output, "I got it in {} guesses!", solver.guesses())?;
+            return Ok(SolverOutcome::Solved {
+                secret: guess,
+                guesses: solver.guesses(),
+            });
+        }
+    }
+}
+
+// Lets the solver find `secret` with honest answers and returns the number of guesses.
+pub fn auto_solve(lower: u32, upper: u32, secret: u32) -> u32 {
+    let mut solver = Solver::new(lower, upper);
+    loop {
+        let guess = solver.next_guess();
+        let answer = guess.cmp(&secret);
+        solver
+            .feedback(guess, answer)
+            .expect("honest answers are always consistent");
+        if answer == Ordering::Equal {
+            return solver.guesses();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Benchmark {
+    pub rounds: u32,
+    pub total_guesses: u64,
+    pub fewest: u32,
+    pub most: u32,
+}
+
+impl Benchmark {
+    pub fn average(&self) -> f64 {
+        self.total_guesses as f64 / self.rounds as f64
+    }
+}
+
+// Plays `rounds` games against secrets from the built-in generator.
+pub fn benchmark<R: Rng>(config: &Config, rounds: u32, rng: &mut R) -> Benchmark {
+    let mut result = Benchmark {
+        rounds,
+        total_guesses: 0,
+        fewest: u32::MAX,
+        most: 0,
+    };
+    for _ in 0..rounds {
+        let secret = game::secret_number(config, rng);
+        let guesses = auto_solve(config.lower, config.upper, secret);
+        result.total_guesses += guesses as u64;
+        result.fewest = result.fewest.min(guesses);
+        result.most = result.most.max(guesses);
+    }
+    result
+}
@@ -0,0 +1,109 @@
+use guessing_game::config::{Config, ConfigError, Difficulty, Mode};
+
+fn parse(args: &[&str]) -> Result<Config, ConfigError> {
+    Config::from_args(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn presets_pick_range_and_attempts() {
+    assert_eq!(Difficulty::Easy.preset(), (1, 50, 10));
+    assert_eq!(Difficulty::Normal.preset(), (1, 100, 7));
+    assert_eq!(Difficulty::Hard.preset(), (1, 1000, 10));
+    assert_eq!(Difficulty::parse("HARD"), Some(Difficulty::Hard));
+    assert_eq!(Difficulty::parse("medium"), None);
+
+    let config = parse(&["--difficulty", "easy"]).unwrap();
+    assert_eq!(
+        (config.lower, config.upper, config.max_attempts),
+        (1, 50, 10)
+    );
+    assert_eq!(
+        Config::default(),
+        Config::from_difficulty(Difficulty::Normal)
+    );
+}
+
+#[test]
+fn options_override_single_preset_values() {
+    let config = parse(&["--difficulty", "hard", "--max", "500"]).unwrap();
+    assert_eq!(
+        (config.lower, config.upper, config.max_attempts),
+        (1, 500, 10)
+    );
+    let config = parse(&["--attempts", "3", "--min", "10"]).unwrap();
+    assert_eq!(
+        (config.lower, config.upper, config.max_attempts),
+        (10, 100, 3)
+    );
+}
+
+#[test]
+fn rejects_an_empty_or_reversed_range() {
+    assert_eq!(
+        parse(&["--min", "100", "--max", "100"]),
+        Err(ConfigError::InvalidRange {
+            lower: 100,
+            upper: 100
+        })
+    );
+    assert_eq!(
+        parse(&["--min", "60", "--difficulty", "easy"]),
+        Err(ConfigError::InvalidRange {
+            lower: 60,
+            upper: 50
+        })
+    );
+}
+
+#[test]
+fn rejects_zero_attempts_and_zero_rounds() {
+    assert_eq!(parse(&["--attempts", "0"]), Err(ConfigError::ZeroAttempts));
+    assert_eq!(parse(&["--auto-play", "0"]), Err(ConfigError::ZeroRounds));
+    assert_eq!(
+        parse(&["--auto-play", "5"]).unwrap().mode,
+        Mode::AutoPlay { rounds: 5 }
+    );
+}
+
+#[test]
+fn validate_checks_configs_built_by_hand() {
+    let mut config = Config::default();
+    assert_eq!(config.validate(), Ok(()));
+    config.lower = 200;
+    assert_eq!(
+        config.validate(),
+        Err(ConfigError::InvalidRange {
+            lower: 200,
+            upper: 100
+        })
+    );
+    config.lower = 1;
+    config.max_attempts = 0;
+    assert_eq!(config.validate(), Err(ConfigError::ZeroAttempts));
+    config.max_attempts = 1;
+    config.mode = Mode::AutoPlay { rounds: 0 };
+    assert_eq!(config.validate(), Err(ConfigError::ZeroRounds));
+}
+
+#[test]
+fn reports_bad_options() {
+    assert_eq!(
+        parse(&["--difficulty", "insane"]),
+        Err(ConfigError::UnknownDifficulty("insane".into()))
+    );
+    assert_eq!(
+        parse(&["--max", "ten"]),
+        Err(ConfigError::InvalidNumber {
+            option: "--max".into(),
+            value: "ten".into()
+        })
+    );
+    assert_eq!(
+        parse(&["--attempts"]),
+        Err(ConfigError::MissingValue("--attempts".into()))
+    );
+    assert_eq!(
+        parse(&["--colour"]),
+        Err(ConfigError::UnknownOption("--colour".into()))
+    );
+}
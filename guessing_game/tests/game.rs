@@ -0,0 +1,68 @@
+use guessing_game::config::Config;
+use guessing_game::game::{self, Outcome};
+
+fn run(config: &Config, secret: u32, input: &str) -> (Outcome, String) {
+    let mut output = Vec::new();
+    let outcome = game::play(config, secret, input.as_bytes(), &mut output).unwrap();
+    (outcome, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn winning_game_transcript() {
+    let config = Config::default();
+    let (outcome, transcript) = run(&config, 42, "50\nabc\n25\n-3\n101\n25\n42\n");
+
+    assert_eq!(outcome, Outcome::Won { attempts: 3 });
+    assert_eq!(
+        transcript,
+        "Guess the number!\n\
+         The secret number is between 1 and 100. You have 7 attempts.\n\
+         Please input your guess.\n\
+         You guessed: 50\n\
+         Too big!\n\
+         Previous guesses: 50\n\
+         The number is between 1 and 49.\n\
+         Please input your guess.\n\
+         'abc' is not a number.\n\
+         Please input your guess.\n\
+         You guessed: 25\n\
+         Too small!\n\
+         Previous guesses: 50, 25\n\
+         The number is between 26 and 49.\n\
+         Please input your guess.\n\
+         -3 is negative; the secret number is never below zero.\n\
+         Please input your guess.\n\
+         101 is out of range; guess between 1 and 100.\n\
+         Please input your guess.\n\
+         You already guessed 25.\n\
+         Please input your guess.\n\
+         You guessed: 42\n\
+         You win!\n"
+    );
+}
+
+#[test]
+fn losing_game_reveals_secret() {
+    let config = Config::from_args(["--attempts", "2"].map(String::from)).unwrap();
+    let (outcome, transcript) = run(&config, 7, "1\n2\n");
+
+    assert_eq!(outcome, Outcome::Lost { secret: 7 });
+    assert!(transcript.ends_with("You lose! You ran out of attempts. The secret number was 7.\n"));
+}
+
+#[test]
+fn end_of_input_abandons_the_game() {
+    let (outcome, _) = run(&Config::default(), 7, "1\n");
+    assert_eq!(outcome, Outcome::Abandoned);
+}
+
+#[test]
+fn same_seed_picks_same_secret() {
+    let config =
+        Config::from_args(["--seed", "1234", "--max", "1000000"].map(String::from)).unwrap();
+
+    let first = game::secret_number(&config, &mut game::make_rng(&config));
+    let second = game::secret_number(&config, &mut game::make_rng(&config));
+    assert_eq!(first, second);
+    assert!((config.lower..=config.upper).contains(&first));
+}
@@ -0,0 +1,148 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+use guessing_game::config::Config;
+use guessing_game::game::Outcome;
+use guessing_game::net::{self, Message, NetError};
+
+// Long enough for a loaded machine, short enough to keep the silent-peer tests quick.
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+// Hosts one game on a free loopback port and returns the address and the host's result.
+fn spawn_host(config: Config, secret: u32) -> (String, thread::JoinHandle<Outcome>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let handle =
+        thread::spawn(move || net::host(&config, secret, &listener, TIMEOUT, Vec::new()).unwrap());
+    (address, handle)
+}
+
+#[test]
+fn guest_wins_over_loopback() {
+    let (address, host) = spawn_host(Config::default(), 42);
+    let mut output = Vec::new();
+    let outcome = net::join(
+        address.as_str(),
+        TIMEOUT,
+        "50\nnope\n25\n42\n".as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+
+    assert_eq!(outcome, Outcome::Won { attempts: 3 });
+    assert_eq!(host.join().unwrap(), Outcome::Won { attempts: 3 });
+    let transcript = String::from_utf8(output).unwrap();
+    assert!(transcript.contains("Too big!\n"));
+    assert!(transcript.contains("'nope' is not a number."));
+    assert!(transcript.ends_with("You win!\n"));
+}
+
+#[test]
+fn guest_loses_over_loopback() {
+    let config = Config::from_args(["--attempts", "2"].map(String::from)).unwrap();
+    let (address, host) = spawn_host(config, 99);
+    let mut output = Vec::new();
+    let outcome = net::join(address.as_str(), TIMEOUT, "1\n2\n".as_bytes(), &mut output).unwrap();
+
+    assert_eq!(outcome, Outcome::Lost { secret: 99 });
+    assert_eq!(host.join().unwrap(), Outcome::Lost { secret: 99 });
+}
+
+#[test]
+fn host_rejects_malformed_messages_and_survives_disconnect() {
+    let (address, host) = spawn_host(Config::default(), 42);
+    let stream = TcpStream::connect(address).unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut stream = stream;
+    let mut line = String::new();
+
+    reader.read_line(&mut line).unwrap();
+    assert_eq!(
+        Message::parse(&line).unwrap(),
+        Message::Hello {
+            lower: 1,
+            upper: 100,
+            attempts: 7
+        }
+    );
+
+    for bad in ["HELLO?", "GUESS ten", "GUESS 500", "EQUAL"] {
+        writeln!(stream, "{bad}").unwrap();
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        assert!(
+            matches!(Message::parse(&line).unwrap(), Message::Error(_)),
+            "{bad} -> {line}"
+        );
+    }
+
+    drop(stream);
+    drop(reader);
+    assert_eq!(host.join().unwrap(), Outcome::Abandoned);
+}
+
+#[test]
+fn guest_survives_host_disconnect() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let host = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        writeln!(stream, "HELLO 1 10 3").unwrap();
+    });
+
+    let outcome = net::join(address, TIMEOUT, "5\n".as_bytes(), Vec::new()).unwrap();
+    host.join().unwrap();
+    assert_eq!(outcome, Outcome::Abandoned);
+}
+
+#[test]
+fn guest_refuses_a_game_that_could_not_end() {
+    for hello in ["HELLO 5 1 3", "HELLO 1 10 0"] {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let host = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            writeln!(stream, "{hello}").unwrap();
+        });
+
+        let result = net::join(address, TIMEOUT, "5\n".as_bytes(), Vec::new());
+        host.join().unwrap();
+        assert!(matches!(result, Err(NetError::Malformed(_))), "{hello}");
+    }
+}
+
+#[test]
+fn silent_peers_abandon_the_game() {
+    // A guest that connects and never guesses.
+    let (address, host) = spawn_host(Config::default(), 42);
+    let guest = TcpStream::connect(address).unwrap();
+    assert_eq!(host.join().unwrap(), Outcome::Abandoned);
+    drop(guest);
+
+    // A host that accepts and never says hello.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let outcome = net::join(address, TIMEOUT, "5\n".as_bytes(), Vec::new()).unwrap();
+    assert_eq!(outcome, Outcome::Abandoned);
+    drop(listener);
+}
+
+#[test]
+fn messages_round_trip() {
+    for message in [
+        Message::Hello {
+            lower: 1,
+            upper: 100,
+            attempts: 7,
+        },
+        Message::Guess(5),
+        Message::Reply(std::cmp::Ordering::Less),
+        Message::Lost { secret: 3 },
+        Message::Error(String::from("bad guess")),
+    ] {
+        assert_eq!(Message::parse(&message.to_string()).unwrap(), message);
+    }
+    assert!(Message::parse("GUESS 1 2").is_err());
+}
@@ -0,0 +1,57 @@
+use std::fs;
+use std::time::Duration;
+
+use guessing_game::scores::{ScoreEntry, ScoreError, ScoreTable, format_date};
+
+fn entry(player: &str, guesses: u32, won: bool, millis: u64) -> ScoreEntry {
+    ScoreEntry::new(player, 1, 100, guesses, won, Duration::from_millis(millis))
+}
+
+#[test]
+fn top_ranks_by_guesses_then_time() {
+    let mut table = ScoreTable::default();
+    table.record(entry("slow", 3, true, 9000));
+    table.record(entry("fast", 3, true, 1000));
+    table.record(entry("lucky", 1, true, 5000));
+    table.record(entry("loser", 7, false, 100));
+
+    let players: Vec<&str> = table.top(10).iter().map(|e| e.player.as_str()).collect();
+    assert_eq!(players, ["lucky", "fast", "slow"]);
+    assert_eq!(table.top(1).len(), 1);
+}
+
+#[test]
+fn save_and_load_round_trip() {
+    let path =
+        std::env::temp_dir().join(format!("guessing_game_scores_{}.txt", std::process::id()));
+    let mut table = ScoreTable::default();
+    table.record(entry("tab\tname", 4, true, 1234));
+    table.save(&path).unwrap();
+
+    let loaded = ScoreTable::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded, table);
+    assert_eq!(loaded.entries()[0].player, "tab name");
+}
+
+#[test]
+fn missing_file_is_empty_and_corrupt_file_is_reported() {
+    let dir = std::env::temp_dir();
+    let missing = dir.join("guessing_game_scores_does_not_exist.txt");
+    assert!(ScoreTable::load(&missing).unwrap().entries().is_empty());
+
+    let corrupt = dir.join(format!(
+        "guessing_game_scores_corrupt_{}.txt",
+        std::process::id()
+    ));
+    fs::write(&corrupt, "not a score line\n").unwrap();
+    let result = ScoreTable::load(&corrupt);
+    fs::remove_file(&corrupt).unwrap();
+    assert!(matches!(result, Err(ScoreError::Corrupt { line: 1 })));
+}
+
+#[test]
+fn dates_are_formatted_in_utc() {
+    assert_eq!(format_date(0), "1970-01-01");
+    assert_eq!(format_date(951_782_400), "2000-02-29");
+}
@@ -0,0 +1,64 @@
+use std::cmp::Ordering;
+
+use guessing_game::config::Config;
+use guessing_game::solver::{self, Solver, SolverError, SolverOutcome};
+
+fn run(input: &str) -> (SolverOutcome, String) {
+    let mut output = Vec::new();
+    let outcome = solver::solve(&Config::default(), input.as_bytes(), &mut output).unwrap();
+    (outcome, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn solves_with_honest_answers() {
+    // Secret 30: 50 -> lower, 25 -> higher, 38 -> lower, 31 -> lower, 28 -> higher, 29 -> higher, 30.
+    let (outcome, transcript) = run("lower\nh\nwhat\nl\nlower\nhigher\nhigher\ncorrect\n");
+
+    assert_eq!(
+        outcome,
+        SolverOutcome::Solved {
+            secret: 30,
+            guesses: 7
+        }
+    );
+    assert!(transcript.contains("Please answer 'higher', 'lower' or 'correct'."));
+    assert!(transcript.ends_with("I got it in 7 guesses!\n"));
+}
+
+#[test]
+fn detects_lying() {
+    // After "lower" for 50 and "higher" for 25, the secret is in 26..=49.
+    // "lower" for 38, 31, 28, 26 leaves nothing below 26.
+    let (outcome, transcript) = run("lower\nhigher\nlower\nlower\nlower\nlower\n");
+
+    assert_eq!(
+        outcome,
+        SolverOutcome::Inconsistent(SolverError::Inconsistent {
+            guess: 26,
+            answer: Ordering::Greater,
+        })
+    );
+    assert!(transcript.contains("You are cheating"));
+}
+
+#[test]
+fn never_needs_more_than_log2_guesses() {
+    for secret in 1..=100 {
+        assert!(solver::auto_solve(1, 100, secret) <= 7);
+    }
+
+    let mut solver = Solver::new(1, 2);
+    assert_eq!(solver.next_guess(), 1);
+    assert!(solver.feedback(1, Ordering::Greater).is_err());
+}
+
+#[test]
+fn benchmark_reports_averages() {
+    let config = Config::from_args(["--seed", "7"].map(String::from)).unwrap();
+    let mut rng = guessing_game::game::make_rng(&config);
+    let result = solver::benchmark(&config, 500, &mut rng);
+
+    assert_eq!(result.rounds, 500);
+    assert!(result.fewest >= 1 && result.most <= 7);
+    assert!(result.average() > 4.0 && result.average() < 7.0);
+}
@@ -0,0 +1,2 @@
+pub mod kitchen;
+pub mod menu;
@@ -0,0 +1,229 @@
+// **** Kitchen ****
+// Orders sent from the front of house become kitchen tickets.
+// Each portion of each dish is cooked at its station, one portion at a time,
+// so a busy grill holds up every order with a burger in it while the cold
+// station may be idle.
+//
+// The Restaurant owns the kitchen: sending an order from the front of house
+// puts a ticket on the queue, serving it takes the ticket off again, and the
+// host can ask how long a new order would wait before taking it.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+
+use crate::back_of_house::menu::{Menu, MenuError, Station};
+use crate::front_of_house::hosting::Restaurant;
+use crate::front_of_house::serving::{self, LineItem, Order, OrderId, ServingError};
+use crate::time::Timestamp;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KitchenTicket {
+    pub order: OrderId,
+    pub received_at: Timestamp,
+    pub ready_at: Timestamp,
+}
+
+impl KitchenTicket {
+    pub fn minutes_left(&self, now: Timestamp) -> u64 {
+        self.ready_at.minutes_since(now)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KitchenError {
+    Menu(MenuError),
+    Serving(ServingError),
+    // The dish would come out after the last moment a Timestamp can hold.
+    ScheduleOverflow { dish: String },
+}
+
+impl fmt::Display for KitchenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KitchenError::Menu(err) => write!(f, "{err}"),
+            KitchenError::Serving(err) => write!(f, "{err}"),
+            KitchenError::ScheduleOverflow { dish } => {
+                write!(f, "'{dish}' would not be ready before the clock runs out")
+            }
+        }
+    }
+}
+
+impl std::error::Error for KitchenError {}
+
+impl From<MenuError> for KitchenError {
+    fn from(err: MenuError) -> Self {
+        KitchenError::Menu(err)
+    }
+}
+
+impl From<ServingError> for KitchenError {
+    fn from(err: ServingError) -> Self {
+        KitchenError::Serving(err)
+    }
+}
+
+// What the front of house needs to quote a wait.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KitchenSummary {
+    pub open_tickets: usize,
+    // Minutes until each station has worked off its backlog.
+    pub station_backlog: Vec<(Station, u64)>,
+    // Minutes until the last open ticket is ready.
+    pub longest_wait: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Kitchen {
+    pub(crate) queue: VecDeque<KitchenTicket>,
+    // When each station finishes what it has already been given.
+    pub(crate) busy_until: BTreeMap<Station, Timestamp>,
+}
+
+impl Kitchen {
+    pub fn new() -> Kitchen {
+        Kitchen::default()
+    }
+
+    // Moves the order to InKitchen and schedules every portion on its station.
+    pub fn submit(
+        &mut self,
+        order: &mut Order,
+        menu: &Menu,
+        now: Timestamp,
+    ) -> Result<&KitchenTicket, KitchenError> {
+        // Check the whole order against the menu before touching any state.
+        let schedule = self.schedule(&order.items, menu, now)?;
+        serving::send_to_kitchen(order)?;
+
+        let ready_at = schedule.finish;
+        self.busy_until = schedule.busy_until;
+        self.queue.push_back(KitchenTicket {
+            order: order.id,
+            received_at: now,
+            ready_at,
+        });
+        Ok(self.queue.back().expect("a ticket was just queued"))
+    }
+
+    // How many minutes an order of `items` would take if it were sent now.
+    pub fn quote(
+        &self,
+        items: &[LineItem],
+        menu: &Menu,
+        now: Timestamp,
+    ) -> Result<u64, KitchenError> {
+        let schedule = self.schedule(items, menu, now)?;
+        Ok(schedule.finish.minutes_since(now))
+    }
+
+    pub fn tickets(&self) -> impl Iterator<Item = &KitchenTicket> {
+        self.queue.iter()
+    }
+
+    pub fn ticket(&self, order: OrderId) -> Option<&KitchenTicket> {
+        self.queue.iter().find(|ticket| ticket.order == order)
+    }
+
+    // Takes the ticket for an order off the queue, ready or not.
+    pub fn take_ticket(&mut self, order: OrderId) -> Option<KitchenTicket> {
+        let index = self.queue.iter().position(|ticket| ticket.order == order)?;
+        self.queue.remove(index)
+    }
+
+    // Takes every ticket that is ready by `now` off the pass, oldest first.
+    pub fn collect_ready(&mut self, now: Timestamp) -> Vec<KitchenTicket> {
+        let (ready, waiting) = self
+            .queue
+            .drain(..)
+            .partition(|ticket| ticket.ready_at <= now);
+        self.queue = waiting;
+        let mut ready: Vec<KitchenTicket> = ready.into_iter().collect();
+        ready.sort_by_key(|ticket| (ticket.ready_at, ticket.received_at));
+        ready
+    }
+
+    pub fn summary(&self, now: Timestamp) -> KitchenSummary {
+        let station_backlog = Station::ALL
+            .iter()
+            .map(|&station| {
+                let busy_until = self.busy_until.get(&station).copied().unwrap_or_default();
+                (station, busy_until.minutes_since(now))
+            })
+            .collect();
+        let longest_wait = self
+            .queue
+            .iter()
+            .map(|ticket| ticket.minutes_left(now))
+            .max()
+            .unwrap_or(0);
+        KitchenSummary {
+            open_tickets: self.queue.len(),
+            station_backlog,
+            longest_wait,
+        }
+    }
+
+    fn schedule(
+        &self,
+        items: &[LineItem],
+        menu: &Menu,
+        now: Timestamp,
+    ) -> Result<Schedule, KitchenError> {
+        let mut busy_until = self.busy_until.clone();
+        let mut finish = now;
+        for item in items {
+            let dish = menu.dish(&item.name)?;
+            let station_free = busy_until.entry(dish.station).or_default();
+            // The portions are cooked back to back from when the station is free.
+            let minutes = dish.prep_minutes as u64 * item.quantity as u64;
+            let done = (*station_free)
+                .max(now)
+                .checked_plus_minutes(minutes)
+                .ok_or_else(|| KitchenError::ScheduleOverflow {
+                    dish: dish.name.clone(),
+                })?;
+            *station_free = done;
+            finish = finish.max(done);
+        }
+        Ok(Schedule { busy_until, finish })
+    }
+}
+
+// The station times after an order is scheduled and the minute it is ready.
+struct Schedule {
+    busy_until: BTreeMap<Station, Timestamp>,
+    finish: Timestamp,
+}
+
+// The restaurant sends its orders to its own kitchen.
+impl Restaurant {
+    pub fn kitchen(&self) -> &Kitchen {
+        &self.kitchen
+    }
+
+    // Sends a taken order to the kitchen and returns its ticket.
+    pub fn send_to_kitchen(
+        &mut self,
+        id: OrderId,
+        menu: &Menu,
+        now: Timestamp,
+    ) -> Result<KitchenTicket, KitchenError> {
+        let order = self
+            .orders
+            .iter_mut()
+            .find(|order| order.id == id)
+            .ok_or(ServingError::UnknownOrder(id))?;
+        Ok(self.kitchen.submit(order, menu, now)?.clone())
+    }
+
+    // How many minutes an order of `items` would wait if it were sent now.
+    pub fn quote_wait(
+        &self,
+        items: &[LineItem],
+        menu: &Menu,
+        now: Timestamp,
+    ) -> Result<u64, KitchenError> {
+        self.kitchen.quote(items, menu, now)
+    }
+}
@@ -0,0 +1,175 @@
+// **** Menu ****
+// Every dish has a price, the minutes it takes to prepare one portion,
+// the kitchen station that cooks it and the ingredients that go into it.
+
+use std::fmt;
+
+use crate::front_of_house::serving::LineItem;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Station {
+    Grill,
+    Fryer,
+    Stove,
+    Cold,
+    Pastry,
+}
+
+impl Station {
+    pub const ALL: [Station; 5] = [
+        Station::Grill,
+        Station::Fryer,
+        Station::Stove,
+        Station::Cold,
+        Station::Pastry,
+    ];
+}
+
+impl fmt::Display for Station {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Station::Grill => "grill",
+            Station::Fryer => "fryer",
+            Station::Stove => "stove",
+            Station::Cold => "cold",
+            Station::Pastry => "pastry",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dish {
+    pub name: String,
+    pub price_cents: u64,
+    pub prep_minutes: u32,
+    pub station: Station,
+    pub ingredients: Vec<String>,
+}
+
+impl Dish {
+    pub fn new(
+        name: &str,
+        price_cents: u64,
+        prep_minutes: u32,
+        station: Station,
+        ingredients: &[&str],
+    ) -> Dish {
+        Dish {
+            name: name.to_string(),
+            price_cents,
+            prep_minutes,
+            station,
+            ingredients: ingredients
+                .iter()
+                .map(|ingredient| ingredient.to_string())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuError {
+    UnknownDish(String),
+    DuplicateDish(String),
+}
+
+impl fmt::Display for MenuError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MenuError::UnknownDish(name) => write!(f, "'{name}' is not on the menu"),
+            MenuError::DuplicateDish(name) => write!(f, "'{name}' is already on the menu"),
+        }
+    }
+}
+
+impl std::error::Error for MenuError {}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Menu {
+    dishes: Vec<Dish>,
+}
+
+impl Menu {
+    pub fn new() -> Menu {
+        Menu::default()
+    }
+
+    // A small menu to open the restaurant with.
+    pub fn house() -> Menu {
+        let mut menu = Menu::new();
+        let dishes = [
+            Dish::new(
+                "Soup",
+                650,
+                5,
+                Station::Stove,
+                &["tomato", "onion", "cream"],
+            ),
+            Dish::new(
+                "Salad",
+                800,
+                4,
+                Station::Cold,
+                &["lettuce", "cucumber", "vinaigrette"],
+            ),
+            Dish::new(
+                "Burger",
+                1450,
+                12,
+                Station::Grill,
+                &["beef", "bun", "cheese"],
+            ),
+            Dish::new(
+                "Steak",
+                2600,
+                18,
+                Station::Grill,
+                &["beef", "butter", "thyme"],
+            ),
+            Dish::new("Fries", 450, 6, Station::Fryer, &["potato", "salt"]),
+            Dish::new(
+                "Pasta",
+                1300,
+                10,
+                Station::Stove,
+                &["pasta", "tomato", "basil"],
+            ),
+            Dish::new("Cake", 700, 3, Station::Pastry, &["flour", "sugar", "egg"]),
+        ];
+        for dish in dishes {
+            menu.add(dish).expect("house dishes have unique names");
+        }
+        menu
+    }
+
+    pub fn add(&mut self, dish: Dish) -> Result<(), MenuError> {
+        if self.find(&dish.name).is_some() {
+            return Err(MenuError::DuplicateDish(dish.name));
+        }
+        self.dishes.push(dish);
+        Ok(())
+    }
+
+    pub fn dishes(&self) -> &[Dish] {
+        &self.dishes
+    }
+
+    // Dish names are matched without regard to case.
+    pub fn find(&self, name: &str) -> Option<&Dish> {
+        self.dishes
+            .iter()
+            .find(|dish| dish.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn dish(&self, name: &str) -> Result<&Dish, MenuError> {
+        self.find(name)
+            .ok_or_else(|| MenuError::UnknownDish(name.to_string()))
+    }
+
+    // Prices an order line from the menu.
+    pub fn line_item(&self, name: &str, quantity: u32) -> Result<LineItem, MenuError> {
+        let dish = self.dish(name)?;
+        Ok(LineItem::new(&dish.name, quantity, dish.price_cents))
+    }
+}
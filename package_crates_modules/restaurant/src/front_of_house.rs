@@ -0,0 +1,4 @@
+pub mod billing;
+pub mod hosting;
+pub mod reservations;
+pub mod serving;
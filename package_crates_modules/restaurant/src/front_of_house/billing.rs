@@ -0,0 +1,299 @@
+// **** Billing ****
+// All amounts are whole cents. Tax and tip are rounded half up to the cent,
+// and when a bill is split the cents that do not divide evenly are handed out
+// one at a time in a fixed order, so the shares always add up to the bill
+// and the same bill always splits the same way.
+
+use std::fmt;
+
+use crate::front_of_house::serving::LineItem;
+
+// Tax is given in basis points (825 = 8.25%) and the tip in whole percent of the subtotal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rates {
+    pub tax_basis_points: u32,
+    pub tip_percent: u32,
+}
+
+impl Default for Rates {
+    fn default() -> Self {
+        Rates {
+            tax_basis_points: 800,
+            tip_percent: 15,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bill {
+    pub items: Vec<LineItem>,
+    pub rates: Rates,
+    pub subtotal_cents: u64,
+    pub tax_cents: u64,
+    pub tip_cents: u64,
+}
+
+// One diner's part of a split bill.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+    pub diner: usize,
+    pub subtotal_cents: u64,
+    pub tax_cents: u64,
+    pub tip_cents: u64,
+}
+
+impl Share {
+    pub fn total_cents(&self) -> u64 {
+        self.subtotal_cents + self.tax_cents + self.tip_cents
+    }
+}
+
+// A diner claims `quantity` portions of the line item at index `item`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Claim {
+    pub diner: usize,
+    pub item: usize,
+    pub quantity: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BillingError {
+    NoDiners,
+    UnknownDiner(usize),
+    UnknownItem(usize),
+    // The claims for an item do not add up to the quantity ordered.
+    ClaimMismatch {
+        item: usize,
+        ordered: u32,
+        claimed: u32,
+    },
+    // A total, tax or tip does not fit in a u64 of cents.
+    AmountTooLarge,
+}
+
+impl fmt::Display for BillingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BillingError::NoDiners => write!(f, "a bill is split between at least one diner"),
+            BillingError::UnknownDiner(diner) => write!(f, "there is no diner {diner}"),
+            BillingError::UnknownItem(item) => write!(f, "there is no item {item} on the bill"),
+            BillingError::ClaimMismatch {
+                item,
+                ordered,
+                claimed,
+            } => write!(
+                f,
+                "item {item} was ordered {ordered} times but claimed {claimed} times"
+            ),
+            BillingError::AmountTooLarge => write!(f, "the amount is too large to bill"),
+        }
+    }
+}
+
+impl std::error::Error for BillingError {}
+
+// Rounds `amount * numerator / denominator` half up.
+fn portion(amount: u64, numerator: u64, denominator: u64) -> Result<u64, BillingError> {
+    amount
+        .checked_mul(numerator)
+        .and_then(|scaled| scaled.checked_add(denominator / 2))
+        .map(|scaled| scaled / denominator)
+        .ok_or(BillingError::AmountTooLarge)
+}
+
+// Shares `total` out in proportion to `weights` so the parts add up to `total`.
+// Each part is rounded down, then the leftover cents go to the largest remainders,
+// ties going to the earlier diner. Zero weights everywhere means equal parts.
+pub fn allocate(total: u64, weights: &[u64]) -> Vec<u64> {
+    let weight_sum: u128 = weights.iter().map(|&weight| weight as u128).sum();
+    if weights.is_empty() {
+        return Vec::new();
+    }
+    if weight_sum == 0 {
+        return allocate(total, &vec![1; weights.len()]);
+    }
+
+    let mut parts: Vec<u64> = Vec::with_capacity(weights.len());
+    let mut remainders: Vec<(u128, usize)> = Vec::with_capacity(weights.len());
+    for (index, &weight) in weights.iter().enumerate() {
+        let exact = total as u128 * weight as u128;
+        parts.push((exact / weight_sum) as u64);
+        remainders.push((exact % weight_sum, index));
+    }
+
+    let leftover = total - parts.iter().sum::<u64>();
+    remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for &(_, index) in remainders.iter().take(leftover as usize) {
+        parts[index] += 1;
+    }
+    parts
+}
+
+pub fn format_cents(cents: u64) -> String {
+    format!("${}.{:02}", cents / 100, cents % 100)
+}
+
+impl Bill {
+    // Fails if the subtotal, tax, tip or their total does not fit in a u64.
+    pub fn new(items: &[LineItem], rates: Rates) -> Result<Bill, BillingError> {
+        let subtotal_cents = items
+            .iter()
+            .try_fold(0u64, |sum, item| {
+                sum.checked_add(item.checked_total_cents()?)
+            })
+            .ok_or(BillingError::AmountTooLarge)?;
+        let tax_cents = portion(subtotal_cents, rates.tax_basis_points as u64, 10_000)?;
+        let tip_cents = portion(subtotal_cents, rates.tip_percent as u64, 100)?;
+        subtotal_cents
+            .checked_add(tax_cents)
+            .and_then(|total| total.checked_add(tip_cents))
+            .ok_or(BillingError::AmountTooLarge)?;
+        Ok(Bill {
+            items: items.to_vec(),
+            rates,
+            subtotal_cents,
+            tax_cents,
+            tip_cents,
+        })
+    }
+
+    pub fn total_cents(&self) -> u64 {
+        self.subtotal_cents + self.tax_cents + self.tip_cents
+    }
+
+    pub fn split_evenly(&self, diners: usize) -> Result<Vec<Share>, BillingError> {
+        if diners == 0 {
+            return Err(BillingError::NoDiners);
+        }
+        // Leftover cents of the subtotal, then the tax, then the tip are handed out
+        // round the table in turn, so no two diners pay more than a cent apart.
+        let mut shares: Vec<Share> = (0..diners)
+            .map(|diner| Share {
+                diner,
+                subtotal_cents: self.subtotal_cents / diners as u64,
+                tax_cents: self.tax_cents / diners as u64,
+                tip_cents: self.tip_cents / diners as u64,
+            })
+            .collect();
+        let mut next = 0;
+        let leftovers = [
+            self.subtotal_cents % diners as u64,
+            self.tax_cents % diners as u64,
+            self.tip_cents % diners as u64,
+        ];
+        for (component, &leftover) in leftovers.iter().enumerate() {
+            for _ in 0..leftover {
+                let share = &mut shares[next % diners];
+                match component {
+                    0 => share.subtotal_cents += 1,
+                    1 => share.tax_cents += 1,
+                    _ => share.tip_cents += 1,
+                }
+                next += 1;
+            }
+        }
+        Ok(shares)
+    }
+
+    // Each diner pays for what they claimed, plus tax and tip in proportion to it.
+    pub fn split_by_items(
+        &self,
+        diners: usize,
+        claims: &[Claim],
+    ) -> Result<Vec<Share>, BillingError> {
+        if diners == 0 {
+            return Err(BillingError::NoDiners);
+        }
+
+        let mut claimed = vec![0u32; self.items.len()];
+        let mut subtotals = vec![0u64; diners];
+        for claim in claims {
+            if claim.diner >= diners {
+                return Err(BillingError::UnknownDiner(claim.diner));
+            }
+            let item = self
+                .items
+                .get(claim.item)
+                .ok_or(BillingError::UnknownItem(claim.item))?;
+            claimed[claim.item] = claimed[claim.item]
+                .checked_add(claim.quantity)
+                .ok_or(BillingError::AmountTooLarge)?;
+            subtotals[claim.diner] = item
+                .unit_price_cents
+                .checked_mul(claim.quantity as u64)
+                .and_then(|cost| subtotals[claim.diner].checked_add(cost))
+                .ok_or(BillingError::AmountTooLarge)?;
+        }
+        for (index, item) in self.items.iter().enumerate() {
+            if claimed[index] != item.quantity {
+                return Err(BillingError::ClaimMismatch {
+                    item: index,
+                    ordered: item.quantity,
+                    claimed: claimed[index],
+                });
+            }
+        }
+
+        let taxes = allocate(self.tax_cents, &subtotals);
+        let tips = allocate(self.tip_cents, &subtotals);
+        Ok((0..diners)
+            .map(|diner| Share {
+                diner,
+                subtotal_cents: subtotals[diner],
+                tax_cents: taxes[diner],
+                tip_cents: tips[diner],
+            })
+            .collect())
+    }
+
+    pub fn render_split(&self, shares: &[Share]) -> String {
+        let mut text = String::new();
+        for share in shares {
+            text.push_str(&format!(
+                "Diner {:<3} {:>10} + tax {:>8} + tip {:>8} = {:>10}\n",
+                share.diner + 1,
+                format_cents(share.subtotal_cents),
+                format_cents(share.tax_cents),
+                format_cents(share.tip_cents),
+                format_cents(share.total_cents())
+            ));
+        }
+        text
+    }
+}
+
+// The plain text receipt.
+impl fmt::Display for Bill {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for item in &self.items {
+            writeln!(
+                f,
+                "{:>3} x {:<20} {:>10}",
+                item.quantity,
+                item.name,
+                format_cents(item.total_cents())
+            )?;
+        }
+        writeln!(f, "{}", "-".repeat(37))?;
+        let tax_label = format!(
+            "Tax ({}.{:02}%)",
+            self.rates.tax_basis_points / 100,
+            self.rates.tax_basis_points % 100
+        );
+        let tip_label = format!("Tip ({}%)", self.rates.tip_percent);
+        writeln!(
+            f,
+            "{:<26} {:>10}",
+            "Subtotal",
+            format_cents(self.subtotal_cents)
+        )?;
+        writeln!(f, "{:<26} {:>10}", tax_label, format_cents(self.tax_cents))?;
+        writeln!(f, "{:<26} {:>10}", tip_label, format_cents(self.tip_cents))?;
+        write!(
+            f,
+            "{:<26} {:>10}",
+            "Total",
+            format_cents(self.total_cents())
+        )
+    }
+}
@@ -0,0 +1,328 @@
+// **** Hosting ****
+// The host keeps a first-come, first-served waitlist of parties
+// and seats the party at the front of the line at the smallest free
+// table that is big enough for it. Parties with a reservation that have
+// checked in are seated first, and tables held for an upcoming booking
+// are not given to walk-ins.
+
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::back_of_house::kitchen::Kitchen;
+use crate::front_of_house::reservations::{Reservation, ReservationId, ReservationState};
+use crate::front_of_house::serving::Order;
+use crate::service_log::{Event, EventKind};
+use crate::time::Timestamp;
+
+// The number handed to a party when it joins the waitlist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ticket(pub u32);
+
+impl fmt::Display for Ticket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TableId(pub usize);
+
+impl fmt::Display for TableId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "T{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Party {
+    pub ticket: Ticket,
+    pub name: String,
+    pub size: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    pub id: TableId,
+    pub capacity: u32,
+    pub(crate) occupant: Option<Party>,
+}
+
+impl Table {
+    pub fn occupant(&self) -> Option<&Party> {
+        self.occupant.as_ref()
+    }
+
+    pub fn is_free(&self) -> bool {
+        self.occupant.is_none()
+    }
+}
+
+// Where a party was seated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seating {
+    pub table: TableId,
+    pub party: Party,
+    pub reservation: Option<ReservationId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostingError {
+    EmptyParty,
+    // The name is empty or only spaces, so the host could not call the party.
+    BlankName,
+    PartyTooLarge { size: u32, largest_table: u32 },
+    WaitlistEmpty,
+    // No free table is big enough for the party at the front of the line.
+    NoTableAvailable { ticket: Ticket, size: u32 },
+    UnknownTicket(Ticket),
+    UnknownTable(TableId),
+    TableNotOccupied(TableId),
+    // The party cannot leave before every order at the table is paid.
+    UnpaidOrders(TableId),
+}
+
+impl fmt::Display for HostingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HostingError::EmptyParty => write!(f, "a party needs at least one guest"),
+            HostingError::BlankName => write!(f, "a party needs a name"),
+            HostingError::PartyTooLarge {
+                size,
+                largest_table,
+            } => write!(
+                f,
+                "a party of {size} does not fit at any table (largest seats {largest_table})"
+            ),
+            HostingError::WaitlistEmpty => write!(f, "nobody is waiting"),
+            HostingError::NoTableAvailable { ticket, size } => {
+                write!(f, "no free table for party {ticket} of {size}")
+            }
+            HostingError::UnknownTicket(ticket) => {
+                write!(f, "no party with ticket {ticket} is waiting")
+            }
+            HostingError::UnknownTable(table) => write!(f, "there is no table {table}"),
+            HostingError::TableNotOccupied(table) => write!(f, "table {table} is not occupied"),
+            HostingError::UnpaidOrders(table) => write!(f, "table {table} still has unpaid orders"),
+        }
+    }
+}
+
+impl std::error::Error for HostingError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Restaurant {
+    pub(crate) tables: Vec<Table>,
+    pub(crate) waitlist: VecDeque<Party>,
+    pub(crate) next_ticket: u32,
+    pub(crate) orders: Vec<Order>,
+    pub(crate) next_order: u32,
+    pub(crate) log: Vec<Event>,
+    pub(crate) reservations: Vec<Reservation>,
+    pub(crate) next_reservation: u32,
+    pub(crate) kitchen: Kitchen,
+}
+
+impl Restaurant {
+    // Creates a restaurant with one table per capacity, numbered from 1.
+    pub fn new(capacities: &[u32]) -> Restaurant {
+        let tables = capacities
+            .iter()
+            .enumerate()
+            .map(|(index, &capacity)| Table {
+                id: TableId(index + 1),
+                capacity,
+                occupant: None,
+            })
+            .collect();
+        Restaurant {
+            tables,
+            waitlist: VecDeque::new(),
+            next_ticket: 1,
+            orders: Vec::new(),
+            next_order: 1,
+            log: Vec::new(),
+            reservations: Vec::new(),
+            next_reservation: 1,
+            kitchen: Kitchen::new(),
+        }
+    }
+
+    pub fn tables(&self) -> &[Table] {
+        &self.tables
+    }
+
+    pub fn table(&self, id: TableId) -> Option<&Table> {
+        self.tables.iter().find(|table| table.id == id)
+    }
+
+    // Everything that happened during service, oldest first.
+    pub fn log(&self) -> &[Event] {
+        &self.log
+    }
+
+    pub(crate) fn record(&mut self, at: Timestamp, kind: EventKind) {
+        self.log.push(Event { at, kind });
+    }
+
+    // Parties in the order they will be seated.
+    pub fn waitlist(&self) -> impl Iterator<Item = &Party> {
+        self.waitlist.iter()
+    }
+
+    pub fn add_to_waitlist(
+        &mut self,
+        name: &str,
+        size: u32,
+        now: Timestamp,
+    ) -> Result<Ticket, HostingError> {
+        if name.trim().is_empty() {
+            return Err(HostingError::BlankName);
+        }
+        if size == 0 {
+            return Err(HostingError::EmptyParty);
+        }
+        let largest_table = self
+            .tables
+            .iter()
+            .map(|table| table.capacity)
+            .max()
+            .unwrap_or(0);
+        if size > largest_table {
+            return Err(HostingError::PartyTooLarge {
+                size,
+                largest_table,
+            });
+        }
+
+        let ticket = Ticket(self.next_ticket);
+        self.next_ticket += 1;
+        self.waitlist.push_back(Party {
+            ticket,
+            name: name.to_string(),
+            size,
+        });
+        self.record(now, EventKind::Joined { ticket, size });
+        Ok(ticket)
+    }
+
+    pub fn leave_waitlist(&mut self, ticket: Ticket) -> Result<Party, HostingError> {
+        let position = self
+            .waitlist
+            .iter()
+            .position(|party| party.ticket == ticket)
+            .ok_or(HostingError::UnknownTicket(ticket))?;
+        Ok(self
+            .waitlist
+            .remove(position)
+            .expect("position is in the waitlist"))
+    }
+
+    // Seats the next party at the best-fitting free table: the smallest one that
+    // seats everybody, so big tables stay free for big parties.
+    // No-shows are released first. Then a checked-in booking is seated, at its own
+    // table if that is free; otherwise the party at the front of the waitlist.
+    pub fn seat_at_table(&mut self, now: Timestamp) -> Result<Seating, HostingError> {
+        self.release_no_shows(now);
+
+        let arrival = self.next_arrival();
+        if let Some((booking, party)) = &arrival {
+            let reserved = self.reservations[*booking].table;
+            let index = match self.tables.iter().position(|table| table.id == reserved) {
+                Some(index) if self.tables[index].is_free() => Some(index),
+                _ => self.best_free_table(party.size, now),
+            };
+            if let Some(index) = index {
+                let reservation = &mut self.reservations[*booking];
+                reservation.state = ReservationState::Seated(party.ticket);
+                let id = reservation.id;
+                return Ok(self.seat(index, party.clone(), Some(id), now));
+            }
+        }
+
+        let party = match (self.waitlist.front(), arrival) {
+            (Some(party), _) => party,
+            (None, Some((_, party))) => {
+                return Err(HostingError::NoTableAvailable {
+                    ticket: party.ticket,
+                    size: party.size,
+                });
+            }
+            (None, None) => return Err(HostingError::WaitlistEmpty),
+        };
+        let index =
+            self.best_free_table(party.size, now)
+                .ok_or(HostingError::NoTableAvailable {
+                    ticket: party.ticket,
+                    size: party.size,
+                })?;
+
+        let party = self
+            .waitlist
+            .pop_front()
+            .expect("the waitlist is not empty");
+        Ok(self.seat(index, party, None, now))
+    }
+
+    fn seat(
+        &mut self,
+        index: usize,
+        party: Party,
+        reservation: Option<ReservationId>,
+        now: Timestamp,
+    ) -> Seating {
+        let table = &mut self.tables[index];
+        table.occupant = Some(party.clone());
+        let table = table.id;
+        self.record(
+            now,
+            EventKind::Seated {
+                ticket: party.ticket,
+                table,
+            },
+        );
+        Seating {
+            table,
+            party,
+            reservation,
+        }
+    }
+
+    // Clears a table when its party leaves and returns who was sitting there.
+    pub fn free_table(&mut self, id: TableId, now: Timestamp) -> Result<Party, HostingError> {
+        if self
+            .orders
+            .iter()
+            .any(|order| order.table == id && order.is_open())
+        {
+            return Err(HostingError::UnpaidOrders(id));
+        }
+        let table = self
+            .tables
+            .iter_mut()
+            .find(|table| table.id == id)
+            .ok_or(HostingError::UnknownTable(id))?;
+        let party = table
+            .occupant
+            .take()
+            .ok_or(HostingError::TableNotOccupied(id))?;
+        self.record(
+            now,
+            EventKind::Left {
+                ticket: party.ticket,
+                table: id,
+            },
+        );
+        Ok(party)
+    }
+
+    fn best_free_table(&self, size: u32, now: Timestamp) -> Option<usize> {
+        self.tables
+            .iter()
+            .enumerate()
+            .filter(|(_, table)| {
+                table.is_free() && table.capacity >= size && !self.held_for_booking(table.id, now)
+            })
+            .min_by_key(|(_, table)| table.capacity)
+            .map(|(index, _)| index)
+    }
+}
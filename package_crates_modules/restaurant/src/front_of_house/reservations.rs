@@ -0,0 +1,341 @@
+// **** Reservations ****
+// A party can book a table in advance for a time slot. Every booking holds
+// its table for SLOT_MINUTES from the start of the slot, and two bookings for
+// the same table may not overlap. A booked party that has not arrived
+// GRACE_MINUTES after the start of its slot is released as a no-show and the
+// table goes back to walk-ins.
+//
+// When a booked party arrives it is checked in and gets a ticket like any
+// walk-in; `seat_at_table` seats checked-in bookings before the waitlist.
+
+use std::fmt;
+
+use crate::front_of_house::hosting::{Party, Restaurant, TableId, Ticket};
+use crate::service_log::EventKind;
+use crate::time::Timestamp;
+
+// How long a booking holds its table.
+pub const SLOT_MINUTES: u64 = 90;
+// How late a booked party may arrive before the booking is released.
+pub const GRACE_MINUTES: u64 = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReservationId(pub u32);
+
+impl fmt::Display for ReservationId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "R{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReservationState {
+    Booked,
+    // The party is here and waiting for its table.
+    Arrived(Ticket),
+    Seated(Ticket),
+    Cancelled,
+    NoShow,
+}
+
+impl fmt::Display for ReservationState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReservationState::Booked => write!(f, "booked"),
+            ReservationState::Arrived(ticket) => write!(f, "arrived ({ticket})"),
+            ReservationState::Seated(ticket) => write!(f, "seated ({ticket})"),
+            ReservationState::Cancelled => write!(f, "cancelled"),
+            ReservationState::NoShow => write!(f, "no-show"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reservation {
+    pub id: ReservationId,
+    pub name: String,
+    pub size: u32,
+    pub table: TableId,
+    pub start: Timestamp,
+    pub(crate) state: ReservationState,
+}
+
+impl Reservation {
+    pub fn state(&self) -> ReservationState {
+        self.state
+    }
+
+    pub fn end(&self) -> Timestamp {
+        self.start.plus_minutes(SLOT_MINUTES)
+    }
+
+    // Cancelled bookings and no-shows no longer hold their table.
+    pub fn holds_table(&self) -> bool {
+        !matches!(
+            self.state,
+            ReservationState::Cancelled | ReservationState::NoShow
+        )
+    }
+
+    fn overlaps(&self, start: Timestamp) -> bool {
+        start < self.end() && self.start < start.plus_minutes(SLOT_MINUTES)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReservationError {
+    EmptyParty,
+    // The booking is under a name that is empty or only spaces.
+    BlankName,
+    SlotInPast {
+        start: Timestamp,
+    },
+    UnknownTable(TableId),
+    TableTooSmall {
+        table: TableId,
+        capacity: u32,
+        size: u32,
+    },
+    // The table is already booked for an overlapping slot.
+    TableBooked {
+        table: TableId,
+        by: ReservationId,
+    },
+    NoTableForSlot {
+        size: u32,
+        start: Timestamp,
+    },
+    UnknownReservation(ReservationId),
+    // Only a booking that is still waiting for its party can be checked in or cancelled.
+    NotBooked {
+        id: ReservationId,
+        state: ReservationState,
+    },
+}
+
+impl fmt::Display for ReservationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReservationError::EmptyParty => write!(f, "a party needs at least one guest"),
+            ReservationError::BlankName => write!(f, "a booking needs a name"),
+            ReservationError::SlotInPast { start } => {
+                write!(f, "the slot at {start} has already started")
+            }
+            ReservationError::UnknownTable(table) => write!(f, "there is no table {table}"),
+            ReservationError::TableTooSmall {
+                table,
+                capacity,
+                size,
+            } => write!(
+                f,
+                "table {table} seats {capacity}, too few for a party of {size}"
+            ),
+            ReservationError::TableBooked { table, by } => {
+                write!(f, "table {table} is already booked by {by} at that time")
+            }
+            ReservationError::NoTableForSlot { size, start } => {
+                write!(f, "no table for a party of {size} is free at {start}")
+            }
+            ReservationError::UnknownReservation(id) => write!(f, "there is no reservation {id}"),
+            ReservationError::NotBooked { id, state } => {
+                write!(f, "reservation {id} is {state}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReservationError {}
+
+impl Restaurant {
+    // Reservations in the order they were booked.
+    pub fn reservations(&self) -> &[Reservation] {
+        &self.reservations
+    }
+
+    pub fn reservation(&self, id: ReservationId) -> Result<&Reservation, ReservationError> {
+        self.reservations
+            .iter()
+            .find(|reservation| reservation.id == id)
+            .ok_or(ReservationError::UnknownReservation(id))
+    }
+
+    // Books the smallest table that seats the party and is free for the whole slot.
+    pub fn book(
+        &mut self,
+        name: &str,
+        size: u32,
+        start: Timestamp,
+        now: Timestamp,
+    ) -> Result<ReservationId, ReservationError> {
+        check_booking(name, size, start, now)?;
+        let table = self
+            .tables
+            .iter()
+            .filter(|table| table.capacity >= size && self.booking_on(table.id, start).is_none())
+            .min_by_key(|table| table.capacity)
+            .map(|table| table.id)
+            .ok_or(ReservationError::NoTableForSlot { size, start })?;
+        Ok(self.add_reservation(name, size, table, start))
+    }
+
+    // Books one particular table, refusing if it is already booked for an overlapping slot.
+    pub fn book_table(
+        &mut self,
+        table: TableId,
+        name: &str,
+        size: u32,
+        start: Timestamp,
+        now: Timestamp,
+    ) -> Result<ReservationId, ReservationError> {
+        check_booking(name, size, start, now)?;
+        let capacity = self
+            .table(table)
+            .ok_or(ReservationError::UnknownTable(table))?
+            .capacity;
+        if capacity < size {
+            return Err(ReservationError::TableTooSmall {
+                table,
+                capacity,
+                size,
+            });
+        }
+        if let Some(by) = self.booking_on(table, start) {
+            return Err(ReservationError::TableBooked { table, by });
+        }
+        Ok(self.add_reservation(name, size, table, start))
+    }
+
+    pub fn cancel_reservation(&mut self, id: ReservationId) -> Result<(), ReservationError> {
+        let reservation = self.booked_mut(id)?;
+        reservation.state = ReservationState::Cancelled;
+        Ok(())
+    }
+
+    // The booked party has arrived: it gets a ticket and waits for `seat_at_table`.
+    // A party arriving after its grace period finds its booking released.
+    pub fn check_in(
+        &mut self,
+        id: ReservationId,
+        now: Timestamp,
+    ) -> Result<Ticket, ReservationError> {
+        self.release_no_shows(now);
+        let ticket = Ticket(self.next_ticket);
+        let reservation = self.booked_mut(id)?;
+        reservation.state = ReservationState::Arrived(ticket);
+        let size = reservation.size;
+        self.next_ticket += 1;
+        self.record(now, EventKind::Joined { ticket, size });
+        Ok(ticket)
+    }
+
+    // Releases every booking whose party is more than GRACE_MINUTES late
+    // and returns the released bookings.
+    pub fn release_no_shows(&mut self, now: Timestamp) -> Vec<Reservation> {
+        let mut released = Vec::new();
+        for reservation in &mut self.reservations {
+            if reservation.state == ReservationState::Booked
+                && now > reservation.start.plus_minutes(GRACE_MINUTES)
+            {
+                reservation.state = ReservationState::NoShow;
+                released.push(reservation.clone());
+            }
+        }
+        released
+    }
+
+    // The checked-in party that has waited longest, by slot start, with its ticket.
+    pub(crate) fn next_arrival(&self) -> Option<(usize, Party)> {
+        self.reservations
+            .iter()
+            .enumerate()
+            .filter_map(|(index, reservation)| match reservation.state {
+                ReservationState::Arrived(ticket) => Some((
+                    index,
+                    Party {
+                        ticket,
+                        name: reservation.name.clone(),
+                        size: reservation.size,
+                    },
+                )),
+                _ => None,
+            })
+            .min_by_key(|(index, _)| self.reservations[*index].start)
+    }
+
+    // Whether `table` must be kept for a booking that has not been seated yet:
+    // a walk-in sat down now would still be eating when that slot starts.
+    pub(crate) fn held_for_booking(&self, table: TableId, now: Timestamp) -> bool {
+        self.reservations.iter().any(|reservation| {
+            reservation.table == table
+                && matches!(
+                    reservation.state,
+                    ReservationState::Booked | ReservationState::Arrived(_)
+                )
+                && reservation.start < now.plus_minutes(SLOT_MINUTES)
+        })
+    }
+
+    fn booking_on(&self, table: TableId, start: Timestamp) -> Option<ReservationId> {
+        self.reservations
+            .iter()
+            .find(|reservation| {
+                reservation.table == table
+                    && reservation.holds_table()
+                    && reservation.overlaps(start)
+            })
+            .map(|reservation| reservation.id)
+    }
+
+    fn booked_mut(&mut self, id: ReservationId) -> Result<&mut Reservation, ReservationError> {
+        let reservation = self
+            .reservations
+            .iter_mut()
+            .find(|reservation| reservation.id == id)
+            .ok_or(ReservationError::UnknownReservation(id))?;
+        if reservation.state != ReservationState::Booked {
+            return Err(ReservationError::NotBooked {
+                id,
+                state: reservation.state,
+            });
+        }
+        Ok(reservation)
+    }
+
+    fn add_reservation(
+        &mut self,
+        name: &str,
+        size: u32,
+        table: TableId,
+        start: Timestamp,
+    ) -> ReservationId {
+        let id = ReservationId(self.next_reservation);
+        self.next_reservation += 1;
+        self.reservations.push(Reservation {
+            id,
+            name: name.to_string(),
+            size,
+            table,
+            start,
+            state: ReservationState::Booked,
+        });
+        id
+    }
+}
+
+fn check_booking(
+    name: &str,
+    size: u32,
+    start: Timestamp,
+    now: Timestamp,
+) -> Result<(), ReservationError> {
+    if name.trim().is_empty() {
+        return Err(ReservationError::BlankName);
+    }
+    if size == 0 {
+        return Err(ReservationError::EmptyParty);
+    }
+    if start < now {
+        return Err(ReservationError::SlotInPast { start });
+    }
+    Ok(())
+}
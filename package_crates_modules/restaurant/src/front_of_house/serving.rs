@@ -0,0 +1,273 @@
+// **** Serving ****
+// A server takes an order from a seated table, sends it to the kitchen,
+// serves it when it comes out and finally takes the payment.
+// Every order moves through Taken -> InKitchen -> Served -> Paid,
+// and the functions below refuse any step out of that order.
+
+use std::fmt;
+
+use crate::front_of_house::billing::{Bill, BillingError, Rates};
+use crate::front_of_house::hosting::{Restaurant, TableId};
+use crate::service_log::EventKind;
+use crate::time::Timestamp;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrderId(pub u32);
+
+impl fmt::Display for OrderId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "O{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderState {
+    Taken,
+    InKitchen,
+    Served,
+    Paid,
+}
+
+impl fmt::Display for OrderState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            OrderState::Taken => "taken",
+            OrderState::InKitchen => "in kitchen",
+            OrderState::Served => "served",
+            OrderState::Paid => "paid",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// Prices are kept in whole cents so totals never pick up rounding errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineItem {
+    pub name: String,
+    pub quantity: u32,
+    pub unit_price_cents: u64,
+}
+
+impl LineItem {
+    pub fn new(name: &str, quantity: u32, unit_price_cents: u64) -> LineItem {
+        LineItem {
+            name: name.to_string(),
+            quantity,
+            unit_price_cents,
+        }
+    }
+
+    // None when the total does not fit in a u64.
+    pub fn checked_total_cents(&self) -> Option<u64> {
+        self.unit_price_cents.checked_mul(self.quantity as u64)
+    }
+
+    // Saturates instead of wrapping; take_order and Bill::new refuse totals
+    // that do not fit, so it only saturates for items made by hand.
+    pub fn total_cents(&self) -> u64 {
+        self.unit_price_cents.saturating_mul(self.quantity as u64)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+// An order can only be changed by moving it through its states, so its
+// items always match what take_order checked and what the bill charged.
+pub struct Order {
+    pub(crate) id: OrderId,
+    pub(crate) table: TableId,
+    pub(crate) items: Vec<LineItem>,
+    pub(crate) state: OrderState,
+    pub(crate) bill: Option<Bill>,
+}
+
+impl Order {
+    pub fn id(&self) -> OrderId {
+        self.id
+    }
+
+    pub fn table(&self) -> TableId {
+        self.table
+    }
+
+    pub fn items(&self) -> &[LineItem] {
+        &self.items
+    }
+
+    pub fn state(&self) -> OrderState {
+        self.state
+    }
+
+    pub fn total_cents(&self) -> u64 {
+        self.items
+            .iter()
+            .map(LineItem::total_cents)
+            .fold(0, u64::saturating_add)
+    }
+
+    // The bill settled when the order was paid.
+    pub fn bill(&self) -> Option<&Bill> {
+        self.bill.as_ref()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state != OrderState::Paid
+    }
+
+    fn advance(&mut self, from: OrderState, to: OrderState) -> Result<(), ServingError> {
+        if self.state != from {
+            return Err(ServingError::IllegalTransition {
+                order: self.id,
+                from: self.state,
+                to,
+            });
+        }
+        self.state = to;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServingError {
+    EmptyOrder,
+    ZeroQuantity(String),
+    // The order would cost more cents than a u64 can count.
+    TotalTooLarge,
+    TableNotSeated(TableId),
+    UnknownOrder(OrderId),
+    IllegalTransition {
+        order: OrderId,
+        from: OrderState,
+        to: OrderState,
+    },
+    Billing(BillingError),
+}
+
+impl fmt::Display for ServingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServingError::EmptyOrder => write!(f, "an order needs at least one item"),
+            ServingError::ZeroQuantity(name) => write!(f, "'{name}' is ordered zero times"),
+            ServingError::TotalTooLarge => write!(f, "the order total is too large"),
+            ServingError::TableNotSeated(table) => write!(f, "nobody is seated at table {table}"),
+            ServingError::UnknownOrder(order) => write!(f, "there is no order {order}"),
+            ServingError::IllegalTransition { order, from, to } => {
+                write!(f, "order {order} is {from} and cannot become {to}")
+            }
+            ServingError::Billing(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for ServingError {}
+
+impl From<BillingError> for ServingError {
+    fn from(err: BillingError) -> Self {
+        ServingError::Billing(err)
+    }
+}
+
+pub fn take_order(
+    id: OrderId,
+    table: TableId,
+    items: Vec<LineItem>,
+) -> Result<Order, ServingError> {
+    if items.is_empty() {
+        return Err(ServingError::EmptyOrder);
+    }
+    if let Some(item) = items.iter().find(|item| item.quantity == 0) {
+        return Err(ServingError::ZeroQuantity(item.name.clone()));
+    }
+    items
+        .iter()
+        .try_fold(0u64, |sum, item| {
+            sum.checked_add(item.checked_total_cents()?)
+        })
+        .ok_or(ServingError::TotalTooLarge)?;
+    Ok(Order {
+        id,
+        table,
+        items,
+        state: OrderState::Taken,
+        bill: None,
+    })
+}
+
+pub fn send_to_kitchen(order: &mut Order) -> Result<(), ServingError> {
+    order.advance(OrderState::Taken, OrderState::InKitchen)
+}
+
+pub fn serve_order(order: &mut Order) -> Result<(), ServingError> {
+    order.advance(OrderState::InKitchen, OrderState::Served)
+}
+
+// Settles a served order with tax and tip and returns the bill that was paid.
+pub fn take_payment(order: &mut Order, rates: Rates) -> Result<Bill, ServingError> {
+    // The bill is worked out first so that an order is never paid without one.
+    let bill = Bill::new(&order.items, rates)?;
+    order.advance(OrderState::Served, OrderState::Paid)?;
+    order.bill = Some(bill.clone());
+    Ok(bill)
+}
+
+// The restaurant keeps every order taken during service.
+impl Restaurant {
+    // Takes an order for a table that currently has a party seated.
+    pub fn take_order(
+        &mut self,
+        table: TableId,
+        items: Vec<LineItem>,
+    ) -> Result<OrderId, ServingError> {
+        let seated = self.table(table).is_some_and(|table| !table.is_free());
+        if !seated {
+            return Err(ServingError::TableNotSeated(table));
+        }
+
+        let id = OrderId(self.next_order);
+        let order = take_order(id, table, items)?;
+        self.next_order += 1;
+        self.orders.push(order);
+        Ok(id)
+    }
+
+    pub fn orders(&self) -> &[Order] {
+        &self.orders
+    }
+
+    pub fn order(&self, id: OrderId) -> Result<&Order, ServingError> {
+        self.orders
+            .iter()
+            .find(|order| order.id == id)
+            .ok_or(ServingError::UnknownOrder(id))
+    }
+
+    pub(crate) fn order_mut(&mut self, id: OrderId) -> Result<&mut Order, ServingError> {
+        self.orders
+            .iter_mut()
+            .find(|order| order.id == id)
+            .ok_or(ServingError::UnknownOrder(id))
+    }
+
+    // Serves an order and takes its ticket off the kitchen queue.
+    pub fn serve(&mut self, id: OrderId) -> Result<(), ServingError> {
+        serve_order(self.order_mut(id)?)?;
+        self.kitchen.take_ticket(id);
+        Ok(())
+    }
+
+    // Takes payment for a served order and notes the time in the service log.
+    pub fn take_payment(
+        &mut self,
+        id: OrderId,
+        rates: Rates,
+        now: Timestamp,
+    ) -> Result<Bill, ServingError> {
+        let bill = take_payment(self.order_mut(id)?, rates)?;
+        self.record(now, EventKind::Paid { order: id });
+        Ok(bill)
+    }
+
+    // Orders that have not been paid yet.
+    pub fn open_orders(&self) -> impl Iterator<Item = &Order> {
+        self.orders.iter().filter(|order| order.is_open())
+    }
+}
@@ -1,15 +1,18 @@
-mod front_of_house {
-    pub mod hosting {
-        pub fn add_to_waitlist() {}
+// The front of house is where customers are: the host seats them
+// and the servers take their orders and payments.
+pub mod front_of_house;
 
-        pub seat_at_table() {}
-    }
+// The back of house is the kitchen: the menu and the cooks working the stations.
+pub mod back_of_house;
 
-    mod serving {
-        fn take_order() {}
+// Saving the state of service to disk and restoring it after a restart.
+pub mod persistence;
 
-        fn serve_order() {}
+// End of day reports worked out from the service log.
+pub mod reporting;
 
-        fn take_payment() {}
-    }
-}
\ No newline at end of file
+// Timestamps, and the log of what happened during service.
+pub mod service_log;
+pub mod time;
+
+pub use crate::front_of_house::hosting::Restaurant;
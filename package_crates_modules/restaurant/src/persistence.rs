@@ -0,0 +1,484 @@
+// **** Saving and Restoring Service ****
+// The restaurant is written to a plain text file, one record per line,
+// so that a restart in the middle of service loses nothing.
+// The first line names the format version. Every version this crate has ever
+// written can still be loaded; a newer version only adds record kinds.
+//
+// Version 1:
+//   restaurant-save 1
+//   counters <next ticket> <next order>
+//   table <id> <capacity>
+//   seated <table id> <ticket> <size> <name>
+//   waiting <ticket> <size> <name>
+//   order <id> <table id> <taken|in_kitchen|served|paid> [<tax basis points> <tip percent>]
+//   item <order id> <quantity> <unit price cents> <name>
+//
+// Version 2 adds the service log, one record per event:
+//   event <unix seconds> joined <ticket> <size>
+//   event <unix seconds> seated <ticket> <table id>
+//   event <unix seconds> left <ticket> <table id>
+//   event <unix seconds> paid <order id>
+//
+// Version 3 adds reservations and a third counter:
+//   counters <next ticket> <next order> <next reservation>
+//   reservation <id> <table id> <start unix seconds> <size> <booked|arrived|seated|cancelled|no_show> [<ticket>] <name>
+//   (the ticket is written for arrived and seated bookings only)
+//
+// Names come last on their line. Up to version 3 a name is the rest of the
+// line after the single space that ends the field before it, so it may
+// contain spaces; line breaks were written as spaces.
+//
+// Version 4 writes names in double quotes, with \" for a quote, \\ for a
+// backslash and \n and \r for line breaks, so every name reads back exactly:
+//   seated 2 7 4 "Ada \"Countess\" Lovelace"
+//
+// Version 5 adds the kitchen: the tickets still on its queue and when each
+// station will be free again:
+//   ticket <order id> <received unix seconds> <ready unix seconds>
+//   station <grill|fryer|stove|cold|pastry> <busy until unix seconds>
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::back_of_house::kitchen::KitchenTicket;
+use crate::back_of_house::menu::Station;
+use crate::front_of_house::billing::{Bill, Rates};
+use crate::front_of_house::hosting::{Party, Restaurant, Table, TableId, Ticket};
+use crate::front_of_house::reservations::{Reservation, ReservationId, ReservationState};
+use crate::front_of_house::serving::{LineItem, Order, OrderId, OrderState};
+use crate::service_log::{Event, EventKind};
+use crate::time::Timestamp;
+
+const HEADER: &str = "restaurant-save";
+pub const CURRENT_VERSION: u32 = 5;
+
+#[derive(Debug)]
+pub enum PersistError {
+    Io(io::Error),
+    MissingHeader,
+    UnsupportedVersion(u32),
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for PersistError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PersistError::Io(err) => write!(f, "could not access the save file: {err}"),
+            PersistError::MissingHeader => write!(f, "this is not a restaurant save file"),
+            PersistError::UnsupportedVersion(version) => write!(
+                f,
+                "save file version {version} is newer than this program (supports up to {CURRENT_VERSION})"
+            ),
+            PersistError::Parse { line, message } => {
+                write!(f, "save file line {line}: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PersistError {}
+
+impl From<io::Error> for PersistError {
+    fn from(err: io::Error) -> Self {
+        PersistError::Io(err)
+    }
+}
+
+fn state_name(state: OrderState) -> &'static str {
+    match state {
+        OrderState::Taken => "taken",
+        OrderState::InKitchen => "in_kitchen",
+        OrderState::Served => "served",
+        OrderState::Paid => "paid",
+    }
+}
+
+// A name in double quotes, escaped so that it stays on one line.
+fn quote(name: &str) -> String {
+    let mut quoted = String::with_capacity(name.len() + 2);
+    quoted.push('"');
+    for c in name.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl Restaurant {
+    pub fn to_save_string(&self) -> String {
+        let mut lines = vec![
+            format!("{HEADER} {CURRENT_VERSION}"),
+            format!(
+                "counters {} {} {}",
+                self.next_ticket, self.next_order, self.next_reservation
+            ),
+        ];
+        for table in &self.tables {
+            lines.push(format!("table {} {}", table.id.0, table.capacity));
+        }
+        for table in &self.tables {
+            if let Some(party) = &table.occupant {
+                lines.push(format!(
+                    "seated {} {} {} {}",
+                    table.id.0,
+                    party.ticket.0,
+                    party.size,
+                    quote(&party.name)
+                ));
+            }
+        }
+        for party in &self.waitlist {
+            lines.push(format!(
+                "waiting {} {} {}",
+                party.ticket.0,
+                party.size,
+                quote(&party.name)
+            ));
+        }
+        for order in &self.orders {
+            let mut line = format!(
+                "order {} {} {}",
+                order.id.0,
+                order.table.0,
+                state_name(order.state)
+            );
+            if let Some(bill) = &order.bill {
+                line.push_str(&format!(
+                    " {} {}",
+                    bill.rates.tax_basis_points, bill.rates.tip_percent
+                ));
+            }
+            lines.push(line);
+            for item in &order.items {
+                lines.push(format!(
+                    "item {} {} {} {}",
+                    order.id.0,
+                    item.quantity,
+                    item.unit_price_cents,
+                    quote(&item.name)
+                ));
+            }
+        }
+        for reservation in &self.reservations {
+            let state = match reservation.state {
+                ReservationState::Booked => String::from("booked"),
+                ReservationState::Arrived(ticket) => format!("arrived {}", ticket.0),
+                ReservationState::Seated(ticket) => format!("seated {}", ticket.0),
+                ReservationState::Cancelled => String::from("cancelled"),
+                ReservationState::NoShow => String::from("no_show"),
+            };
+            lines.push(format!(
+                "reservation {} {} {} {} {state} {}",
+                reservation.id.0,
+                reservation.table.0,
+                reservation.start.0,
+                reservation.size,
+                quote(&reservation.name)
+            ));
+        }
+        for ticket in &self.kitchen.queue {
+            lines.push(format!(
+                "ticket {} {} {}",
+                ticket.order.0, ticket.received_at.0, ticket.ready_at.0
+            ));
+        }
+        for (station, busy_until) in &self.kitchen.busy_until {
+            lines.push(format!("station {station} {}", busy_until.0));
+        }
+        for event in &self.log {
+            let kind = match event.kind {
+                EventKind::Joined { ticket, size } => format!("joined {} {size}", ticket.0),
+                EventKind::Seated { ticket, table } => format!("seated {} {}", ticket.0, table.0),
+                EventKind::Left { ticket, table } => format!("left {} {}", ticket.0, table.0),
+                EventKind::Paid { order } => format!("paid {}", order.0),
+            };
+            lines.push(format!("event {} {kind}", event.at.0));
+        }
+        let mut text = lines.join("\n");
+        text.push('\n');
+        text
+    }
+
+    pub fn from_save_str(text: &str) -> Result<Restaurant, PersistError> {
+        let mut lines = text.lines().enumerate();
+        let version = match lines.next().map(|(_, line)| line.split_once(' ')) {
+            Some(Some((HEADER, version))) => version
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| PersistError::MissingHeader)?,
+            _ => return Err(PersistError::MissingHeader),
+        };
+        if version == 0 || version > CURRENT_VERSION {
+            return Err(PersistError::UnsupportedVersion(version));
+        }
+
+        let mut restaurant = Restaurant::new(&[]);
+        for (index, line) in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+            restaurant
+                .load_record(line, version)
+                .map_err(|message| PersistError::Parse {
+                    line: index + 1,
+                    message,
+                })?;
+        }
+        Ok(restaurant)
+    }
+
+    // Writes to a temporary file first so a crash never leaves half a save behind.
+    pub fn save(&self, path: &Path) -> Result<(), PersistError> {
+        let temporary = path.with_extension("tmp");
+        fs::write(&temporary, self.to_save_string())?;
+        fs::rename(&temporary, path)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Restaurant, PersistError> {
+        Restaurant::from_save_str(&fs::read_to_string(path)?)
+    }
+
+    fn load_record(&mut self, line: &str, version: u32) -> Result<(), String> {
+        let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let mut fields = Fields::new(rest, version);
+        match kind {
+            "counters" => {
+                self.next_ticket = fields.number("next ticket")?;
+                self.next_order = fields.number("next order")?;
+                // Files from before version 3 have no reservations to number.
+                if !fields.is_empty() {
+                    self.next_reservation = fields.number("next reservation")?;
+                }
+            }
+            "table" => {
+                let id = TableId(fields.number("table id")?);
+                if self.table(id).is_some() {
+                    return Err(format!("table {id} is listed twice"));
+                }
+                self.tables.push(Table {
+                    id,
+                    capacity: fields.number("capacity")?,
+                    occupant: None,
+                });
+            }
+            "seated" => {
+                let id = TableId(fields.number("table id")?);
+                let party = fields.party()?;
+                let table = self
+                    .tables
+                    .iter_mut()
+                    .find(|table| table.id == id)
+                    .ok_or(format!("party seated at unknown table {id}"))?;
+                table.occupant = Some(party);
+            }
+            "waiting" => {
+                let party = fields.party()?;
+                self.waitlist.push_back(party);
+            }
+            "order" => {
+                let id = OrderId(fields.number("order id")?);
+                let table = TableId(fields.number("table id")?);
+                if self.table(table).is_none() {
+                    return Err(format!("order {id} is for unknown table {table}"));
+                }
+                let state = match fields.word("order state")? {
+                    "taken" => OrderState::Taken,
+                    "in_kitchen" => OrderState::InKitchen,
+                    "served" => OrderState::Served,
+                    "paid" => OrderState::Paid,
+                    other => return Err(format!("unknown order state '{other}'")),
+                };
+                // The bill itself is worked out again from the items once they are read.
+                let bill = if state == OrderState::Paid {
+                    let rates = Rates {
+                        tax_basis_points: fields.number("tax rate")?,
+                        tip_percent: fields.number("tip percent")?,
+                    };
+                    Some(Bill::new(&[], rates).map_err(|err| err.to_string())?)
+                } else {
+                    None
+                };
+                self.orders.push(Order {
+                    id,
+                    table,
+                    items: Vec::new(),
+                    state,
+                    bill,
+                });
+            }
+            "item" => {
+                let id = OrderId(fields.number("order id")?);
+                let quantity = fields.number("quantity")?;
+                let unit_price_cents = fields.number("unit price")?;
+                let name = fields.name("item name")?;
+                let order = self
+                    .orders
+                    .iter_mut()
+                    .find(|order| order.id == id)
+                    .ok_or(format!("item for unknown order {id}"))?;
+                order
+                    .items
+                    .push(LineItem::new(&name, quantity, unit_price_cents));
+                if let Some(bill) = &order.bill {
+                    let bill = Bill::new(&order.items, bill.rates)
+                        .map_err(|err| format!("order {id}: {err}"))?;
+                    order.bill = Some(bill);
+                }
+            }
+            "reservation" => {
+                let id = ReservationId(fields.number("reservation id")?);
+                let table = TableId(fields.number("table id")?);
+                if self.table(table).is_none() {
+                    return Err(format!("reservation {id} is for unknown table {table}"));
+                }
+                let start = Timestamp(fields.number("slot start")?);
+                let size = fields.number("party size")?;
+                let state = match fields.word("reservation state")? {
+                    "booked" => ReservationState::Booked,
+                    "arrived" => ReservationState::Arrived(Ticket(fields.number("ticket")?)),
+                    "seated" => ReservationState::Seated(Ticket(fields.number("ticket")?)),
+                    "cancelled" => ReservationState::Cancelled,
+                    "no_show" => ReservationState::NoShow,
+                    other => return Err(format!("unknown reservation state '{other}'")),
+                };
+                self.reservations.push(Reservation {
+                    id,
+                    name: fields.name("party name")?,
+                    size,
+                    table,
+                    start,
+                    state,
+                });
+            }
+            "ticket" => {
+                let order = OrderId(fields.number("order id")?);
+                if self.order(order).is_err() {
+                    return Err(format!("kitchen ticket for unknown order {order}"));
+                }
+                self.kitchen.queue.push_back(KitchenTicket {
+                    order,
+                    received_at: Timestamp(fields.number("time received")?),
+                    ready_at: Timestamp(fields.number("time ready")?),
+                });
+            }
+            "station" => {
+                let name = fields.word("station")?;
+                let station = Station::ALL
+                    .into_iter()
+                    .find(|station| station.to_string() == name)
+                    .ok_or(format!("unknown station '{name}'"))?;
+                let busy_until = Timestamp(fields.number("busy until")?);
+                self.kitchen.busy_until.insert(station, busy_until);
+            }
+            "event" => {
+                let at = Timestamp(fields.number("event time")?);
+                let kind = match fields.word("event kind")? {
+                    "joined" => EventKind::Joined {
+                        ticket: Ticket(fields.number("ticket")?),
+                        size: fields.number("party size")?,
+                    },
+                    "seated" => EventKind::Seated {
+                        ticket: Ticket(fields.number("ticket")?),
+                        table: TableId(fields.number("table id")?),
+                    },
+                    "left" => EventKind::Left {
+                        ticket: Ticket(fields.number("ticket")?),
+                        table: TableId(fields.number("table id")?),
+                    },
+                    "paid" => EventKind::Paid {
+                        order: OrderId(fields.number("order id")?),
+                    },
+                    other => return Err(format!("unknown event '{other}'")),
+                };
+                self.log.push(Event { at, kind });
+            }
+            other => return Err(format!("unknown record '{other}'")),
+        }
+        Ok(())
+    }
+}
+
+// Reads the space separated fields of one record, in order.
+struct Fields<'a> {
+    rest: &'a str,
+    // The version of the file, which decides how names are written.
+    version: u32,
+}
+
+impl<'a> Fields<'a> {
+    fn new(rest: &'a str, version: u32) -> Fields<'a> {
+        Fields { rest, version }
+    }
+
+    fn word(&mut self, what: &str) -> Result<&'a str, String> {
+        let rest = self.rest.trim_start();
+        if rest.is_empty() {
+            return Err(format!("missing {what}"));
+        }
+        let (word, rest) = rest.split_once(' ').unwrap_or((rest, ""));
+        self.rest = rest;
+        Ok(word)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rest.trim().is_empty()
+    }
+
+    fn number<T: FromStr>(&mut self, what: &str) -> Result<T, String> {
+        let word = self.word(what)?;
+        word.parse().map_err(|_| format!("invalid {what} '{word}'"))
+    }
+
+    // The name that ends the record, exactly as it was saved.
+    fn name(&mut self, what: &str) -> Result<String, String> {
+        let rest = std::mem::take(&mut self.rest);
+        if self.version < 4 {
+            return Ok(rest.to_string());
+        }
+
+        let mut chars = rest.trim_start().chars();
+        if chars.next() != Some('"') {
+            return Err(format!("missing {what}"));
+        }
+        let mut name = String::new();
+        loop {
+            match chars.next() {
+                Some('"') => break,
+                Some('\\') => match chars.next() {
+                    Some('"') => name.push('"'),
+                    Some('\\') => name.push('\\'),
+                    Some('n') => name.push('\n'),
+                    Some('r') => name.push('\r'),
+                    other => {
+                        let escape = other.map(String::from).unwrap_or_default();
+                        return Err(format!("invalid escape '\\{escape}' in {what}"));
+                    }
+                },
+                Some(c) => name.push(c),
+                None => return Err(format!("unterminated {what}")),
+            }
+        }
+        if !chars.as_str().trim().is_empty() {
+            return Err(format!("unexpected text after {what}"));
+        }
+        Ok(name)
+    }
+
+    fn party(&mut self) -> Result<Party, String> {
+        Ok(Party {
+            ticket: Ticket(self.number("ticket")?),
+            size: self.number("party size")?,
+            name: self.name("party name")?,
+        })
+    }
+}
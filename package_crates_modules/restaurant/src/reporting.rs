@@ -0,0 +1,345 @@
+// **** Day-End Report ****
+// Worked out from the service log: what was paid in each hour, how big the
+// parties were and how long they waited to be seated, how often each table
+// turned over, and which dishes sold best. Revenue is the food and drink
+// subtotal; tax and tips are reported next to it.
+//
+// A report covers one period, usually a day in the restaurant's own time
+// zone. Only what happened in the period is counted, but a party that joined
+// the waitlist before the period started still has its whole wait counted.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::front_of_house::billing::format_cents;
+use crate::front_of_house::hosting::{Restaurant, TableId, Ticket};
+use crate::service_log::EventKind;
+use crate::time::Timestamp;
+
+// From `start` up to but not including `end`. Hours are reported in local
+// time, `utc_offset_minutes` east of UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReportPeriod {
+    pub start: Timestamp,
+    pub end: Timestamp,
+    pub utc_offset_minutes: i32,
+}
+
+impl ReportPeriod {
+    // Local midnight to midnight of the given date, or None if there is no such
+    // date or the day does not fit between the epoch and the last Timestamp.
+    pub fn day(year: i64, month: u32, day: u32, utc_offset_minutes: i32) -> Option<ReportPeriod> {
+        let midnight = Timestamp::from_date_time(year, month, day, 0, 0)?;
+        let start = midnight
+            .0
+            .checked_add_signed(-(utc_offset_minutes as i64) * 60)?;
+        Some(ReportPeriod {
+            start: Timestamp(start),
+            end: Timestamp(start.checked_add(86_400)?),
+            utc_offset_minutes,
+        })
+    }
+
+    pub fn contains(&self, at: Timestamp) -> bool {
+        self.start <= at && at < self.end
+    }
+
+    // The local hour of the day at `at`.
+    pub fn hour_of_day(&self, at: Timestamp) -> u8 {
+        let local = at.0 as i128 + self.utc_offset_minutes as i128 * 60;
+        (local.rem_euclid(86_400) / 3600) as u8
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HourRevenue {
+    pub hour: u8,
+    pub orders: u32,
+    pub revenue_cents: u64,
+    pub tax_cents: u64,
+    pub tip_cents: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableTurnover {
+    pub table: TableId,
+    pub capacity: u32,
+    pub parties: u32,
+    // Average minutes from seating to leaving, over the parties that have left.
+    pub average_minutes_seated: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DishSales {
+    pub name: String,
+    pub quantity: u32,
+    pub revenue_cents: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayReport {
+    pub revenue_by_hour: Vec<HourRevenue>,
+    pub parties_seated: u32,
+    pub average_party_size: Option<f64>,
+    pub average_wait_minutes: Option<f64>,
+    pub turnover: Vec<TableTurnover>,
+    pub best_sellers: Vec<DishSales>,
+}
+
+fn average(total: u64, count: u64) -> Option<f64> {
+    if count == 0 {
+        None
+    } else {
+        Some(total as f64 / count as f64)
+    }
+}
+
+fn show(value: Option<f64>) -> String {
+    value.map_or_else(|| String::from("-"), |value| format!("{value:.1}"))
+}
+
+impl DayReport {
+    pub fn build(restaurant: &Restaurant, period: ReportPeriod) -> DayReport {
+        let mut hours: BTreeMap<u8, HourRevenue> = BTreeMap::new();
+        let mut dishes: HashMap<String, DishSales> = HashMap::new();
+        let mut joined: HashMap<Ticket, (Timestamp, u32)> = HashMap::new();
+        let mut seated_at: HashMap<Ticket, Timestamp> = HashMap::new();
+        let (mut parties_seated, mut sized_parties, mut guests) = (0, 0, 0);
+        let (mut waited, mut total_wait) = (0, 0);
+        let mut per_table: HashMap<TableId, (u32, u64, u64)> = HashMap::new();
+
+        // Earlier events are read too, for the joining and seating times.
+        for event in restaurant.log() {
+            if event.at >= period.end {
+                continue;
+            }
+            let counted = period.contains(event.at);
+            match event.kind {
+                EventKind::Joined { ticket, size } => {
+                    joined.insert(ticket, (event.at, size));
+                }
+                EventKind::Seated { ticket, table } => {
+                    seated_at.insert(ticket, event.at);
+                    if !counted {
+                        continue;
+                    }
+                    parties_seated += 1;
+                    per_table.entry(table).or_default().0 += 1;
+                    if let Some(&(joined_at, size)) = joined.get(&ticket) {
+                        sized_parties += 1;
+                        guests += size as u64;
+                        waited += 1;
+                        total_wait += event.at.minutes_since(joined_at);
+                    }
+                }
+                EventKind::Left { ticket, table } => {
+                    if !counted {
+                        continue;
+                    }
+                    if let Some(&seated) = seated_at.get(&ticket) {
+                        let entry = per_table.entry(table).or_default();
+                        entry.1 += 1;
+                        entry.2 += event.at.minutes_since(seated);
+                    }
+                }
+                EventKind::Paid { order } => {
+                    if !counted {
+                        continue;
+                    }
+                    let Some(bill) = restaurant.order(order).ok().and_then(|order| order.bill())
+                    else {
+                        continue;
+                    };
+                    let hour = period.hour_of_day(event.at);
+                    let revenue = hours.entry(hour).or_insert_with(|| HourRevenue {
+                        hour,
+                        ..HourRevenue::default()
+                    });
+                    revenue.orders += 1;
+                    revenue.revenue_cents += bill.subtotal_cents;
+                    revenue.tax_cents += bill.tax_cents;
+                    revenue.tip_cents += bill.tip_cents;
+
+                    for item in &bill.items {
+                        let sales = dishes
+                            .entry(item.name.clone())
+                            .or_insert_with(|| DishSales {
+                                name: item.name.clone(),
+                                quantity: 0,
+                                revenue_cents: 0,
+                            });
+                        sales.quantity += item.quantity;
+                        sales.revenue_cents += item.total_cents();
+                    }
+                }
+            }
+        }
+
+        let turnover = restaurant
+            .tables()
+            .iter()
+            .map(|table| {
+                let (parties, left, minutes) =
+                    per_table.get(&table.id).copied().unwrap_or_default();
+                TableTurnover {
+                    table: table.id,
+                    capacity: table.capacity,
+                    parties,
+                    average_minutes_seated: average(minutes, left),
+                }
+            })
+            .collect();
+
+        let mut best_sellers: Vec<DishSales> = dishes.into_values().collect();
+        best_sellers.sort_by(|a, b| {
+            b.quantity
+                .cmp(&a.quantity)
+                .then(b.revenue_cents.cmp(&a.revenue_cents))
+                .then(a.name.cmp(&b.name))
+        });
+
+        DayReport {
+            revenue_by_hour: hours.into_values().collect(),
+            parties_seated,
+            average_party_size: average(guests, sized_parties),
+            average_wait_minutes: average(total_wait, waited),
+            turnover,
+            best_sellers,
+        }
+    }
+
+    pub fn total_revenue_cents(&self) -> u64 {
+        self.revenue_by_hour
+            .iter()
+            .map(|hour| hour.revenue_cents)
+            .sum()
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![
+            String::from("End of day report"),
+            String::new(),
+            format!("Parties seated:      {}", self.parties_seated),
+            format!("Average party size:  {}", show(self.average_party_size)),
+            format!(
+                "Average wait:        {} min",
+                show(self.average_wait_minutes)
+            ),
+            format!(
+                "Revenue:             {}",
+                format_cents(self.total_revenue_cents())
+            ),
+            String::new(),
+            String::from("Revenue by hour"),
+            format!(
+                "{:<6} {:>6} {:>10} {:>9} {:>9}",
+                "Hour", "Orders", "Revenue", "Tax", "Tips"
+            ),
+        ];
+        for hour in &self.revenue_by_hour {
+            lines.push(format!(
+                "{:02}:00  {:>6} {:>10} {:>9} {:>9}",
+                hour.hour,
+                hour.orders,
+                format_cents(hour.revenue_cents),
+                format_cents(hour.tax_cents),
+                format_cents(hour.tip_cents)
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push(String::from("Table turnover"));
+        lines.push(format!(
+            "{:<6} {:>5} {:>8} {:>12}",
+            "Table", "Seats", "Parties", "Avg minutes"
+        ));
+        for table in &self.turnover {
+            lines.push(format!(
+                "{:<6} {:>5} {:>8} {:>12}",
+                table.table.to_string(),
+                table.capacity,
+                table.parties,
+                show(table.average_minutes_seated)
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push(String::from("Best sellers"));
+        lines.push(format!("{:<20} {:>5} {:>10}", "Dish", "Sold", "Revenue"));
+        for dish in &self.best_sellers {
+            lines.push(format!(
+                "{:<20} {:>5} {:>10}",
+                dish.name,
+                dish.quantity,
+                format_cents(dish.revenue_cents)
+            ));
+        }
+
+        let mut text = lines.join("\n");
+        text.push('\n');
+        text
+    }
+
+    // One CSV table with the columns section,name,count,value.
+    // Money is in cents and averages have one decimal.
+    pub fn to_csv(&self) -> String {
+        let mut rows = vec![
+            String::from("section,name,count,value"),
+            format!("summary,parties_seated,{},", self.parties_seated),
+            format!(
+                "summary,average_party_size,,{}",
+                csv_number(self.average_party_size)
+            ),
+            format!(
+                "summary,average_wait_minutes,,{}",
+                csv_number(self.average_wait_minutes)
+            ),
+            format!("summary,revenue_cents,,{}", self.total_revenue_cents()),
+        ];
+        for hour in &self.revenue_by_hour {
+            rows.push(format!(
+                "revenue_by_hour,{:02}:00,{},{}",
+                hour.hour, hour.orders, hour.revenue_cents
+            ));
+            rows.push(format!(
+                "tax_by_hour,{:02}:00,{},{}",
+                hour.hour, hour.orders, hour.tax_cents
+            ));
+            rows.push(format!(
+                "tips_by_hour,{:02}:00,{},{}",
+                hour.hour, hour.orders, hour.tip_cents
+            ));
+        }
+        for table in &self.turnover {
+            rows.push(format!(
+                "table_turnover,{},{},{}",
+                table.table,
+                table.parties,
+                csv_number(table.average_minutes_seated)
+            ));
+        }
+        for dish in &self.best_sellers {
+            rows.push(format!(
+                "best_sellers,{},{},{}",
+                csv_field(&dish.name),
+                dish.quantity,
+                dish.revenue_cents
+            ));
+        }
+        let mut csv = rows.join("\n");
+        csv.push('\n');
+        csv
+    }
+}
+
+fn csv_number(value: Option<f64>) -> String {
+    value.map_or_else(String::new, |value| format!("{value:.1}"))
+}
+
+// Quotes a field that contains a comma, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
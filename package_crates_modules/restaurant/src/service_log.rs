@@ -0,0 +1,21 @@
+// **** Service Log ****
+// Everything that happens to a party or a bill during service is written down
+// with the time it happened. Reports are worked out from this log afterwards.
+
+use crate::front_of_house::hosting::{TableId, Ticket};
+use crate::front_of_house::serving::OrderId;
+use crate::time::Timestamp;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Joined { ticket: Ticket, size: u32 },
+    Seated { ticket: Ticket, table: TableId },
+    Left { ticket: Ticket, table: TableId },
+    Paid { order: OrderId },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event {
+    pub at: Timestamp,
+    pub kind: EventKind,
+}
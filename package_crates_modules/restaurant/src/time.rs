@@ -0,0 +1,127 @@
+// **** Time ****
+// Points in time are whole seconds since the unix epoch, in UTC.
+// The library never reads the clock itself: every call that needs the time
+// is told what time it is, so service can be replayed and tested.
+
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Timestamp(pub u64);
+
+impl Timestamp {
+    // The system clock, for callers that run in real time.
+    pub fn now() -> Timestamp {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_secs())
+            .unwrap_or(0);
+        Timestamp(seconds)
+    }
+
+    // The given UTC date and time, or None if there is no such moment
+    // (month 13, February 30th, 25:00, or before the epoch).
+    pub fn from_date_time(
+        year: i64,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+    ) -> Option<Timestamp> {
+        if !(1..=12).contains(&month) || day == 0 || hour > 23 || minute > 59 {
+            return None;
+        }
+        let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+        let days_in_month = match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        if day > days_in_month {
+            return None;
+        }
+
+        // Civil date to days conversion, the inverse of `date` below.
+        // Years too far out for a Timestamp fail the checked steps.
+        let year = if month <= 2 {
+            year.checked_sub(1)?
+        } else {
+            year
+        };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let mp = if month > 2 { month - 3 } else { month + 9 } as i64;
+        let day_of_year = (153 * mp + 2) / 5 + day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era
+            .checked_mul(146_097)?
+            .checked_add(day_of_era - 719_468)?;
+        if days < 0 {
+            return None;
+        }
+        let seconds = (days as u64)
+            .checked_mul(86_400)?
+            .checked_add(hour as u64 * 3600 + minute as u64 * 60)?;
+        Some(Timestamp(seconds))
+    }
+
+    // Both saturate at the last second a Timestamp can hold, which a
+    // save file can contain.
+    pub fn from_minutes(minutes: u64) -> Timestamp {
+        Timestamp(minutes.saturating_mul(60))
+    }
+
+    pub fn plus_minutes(self, minutes: u64) -> Timestamp {
+        Timestamp(self.0.saturating_add(minutes.saturating_mul(60)))
+    }
+
+    // None if the result is past the last second a Timestamp can hold.
+    pub fn checked_plus_minutes(self, minutes: u64) -> Option<Timestamp> {
+        minutes
+            .checked_mul(60)
+            .and_then(|seconds| self.0.checked_add(seconds))
+            .map(Timestamp)
+    }
+
+    // Whole minutes from `earlier` to `self`, zero if `earlier` is later.
+    pub fn minutes_since(self, earlier: Timestamp) -> u64 {
+        self.0.saturating_sub(earlier.0) / 60
+    }
+
+    pub fn hour_of_day(self) -> u8 {
+        ((self.0 / 3600) % 24) as u8
+    }
+
+    pub fn minute_of_hour(self) -> u8 {
+        ((self.0 / 60) % 60) as u8
+    }
+
+    // Days since the epoch as (year, month, day).
+    pub fn date(self) -> (i64, u32, u32) {
+        // Days to civil date conversion from Howard Hinnant's date algorithms.
+        let days = (self.0 / 86_400) as i64 + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        (year, month, day)
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (year, month, day) = self.date();
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+            self.hour_of_day(),
+            self.minute_of_hour()
+        )
+    }
+}
@@ -0,0 +1,207 @@
+use restaurant::front_of_house::billing::{Bill, BillingError, Claim, Rates, Share, allocate};
+use restaurant::front_of_house::hosting::TableId;
+use restaurant::front_of_house::serving::{self, LineItem, OrderId, OrderState, ServingError};
+
+fn bill(items: &[(&str, u32, u64)], tax_basis_points: u32, tip_percent: u32) -> Bill {
+    let items: Vec<LineItem> = items
+        .iter()
+        .map(|&(name, quantity, price)| LineItem::new(name, quantity, price))
+        .collect();
+    Bill::new(
+        &items,
+        Rates {
+            tax_basis_points,
+            tip_percent,
+        },
+    )
+    .unwrap()
+}
+
+fn sums(shares: &[Share]) -> (u64, u64, u64, u64) {
+    shares
+        .iter()
+        .fold((0, 0, 0, 0), |(sub, tax, tip, total), share| {
+            (
+                sub + share.subtotal_cents,
+                tax + share.tax_cents,
+                tip + share.tip_cents,
+                total + share.total_cents(),
+            )
+        })
+}
+
+fn assert_adds_up(bill: &Bill, shares: &[Share]) {
+    assert_eq!(
+        sums(shares),
+        (
+            bill.subtotal_cents,
+            bill.tax_cents,
+            bill.tip_cents,
+            bill.total_cents()
+        )
+    );
+}
+
+#[test]
+fn rounds_tax_and_tip_half_up() {
+    // 8.25% of $10.01 is 82.5825 cents, 15% is 150.15 cents.
+    let bill = bill(&[("Soup", 1, 1001)], 825, 15);
+    assert_eq!(
+        (bill.subtotal_cents, bill.tax_cents, bill.tip_cents),
+        (1001, 83, 150)
+    );
+    assert_eq!(bill.total_cents(), 1234);
+}
+
+#[test]
+fn even_split_hands_out_leftover_cents_in_turn() {
+    // 1001 + 80 + 150 cents between three diners leaves 2, 2 and 0 cents over.
+    let bill = bill(&[("Soup", 1, 1001)], 800, 15);
+    let shares = bill.split_evenly(3).unwrap();
+    assert_adds_up(&bill, &shares);
+    let totals: Vec<u64> = shares.iter().map(Share::total_cents).collect();
+    assert_eq!(totals, [411, 410, 410]);
+    assert_eq!((shares[2].subtotal_cents, shares[2].tax_cents), (333, 27));
+
+    for diners in 1..=12 {
+        let shares = bill.split_evenly(diners).unwrap();
+        assert_adds_up(&bill, &shares);
+        let most = shares.iter().map(Share::total_cents).max().unwrap();
+        let least = shares.iter().map(Share::total_cents).min().unwrap();
+        assert!(most - least <= 1, "{diners} diners");
+    }
+    assert_eq!(bill.split_evenly(0), Err(BillingError::NoDiners));
+}
+
+#[test]
+fn item_split_charges_what_each_diner_claimed() {
+    let bill = bill(&[("Burger", 2, 1450), ("Fries", 3, 450)], 825, 18);
+    let claims = [
+        Claim {
+            diner: 0,
+            item: 0,
+            quantity: 1,
+        },
+        Claim {
+            diner: 1,
+            item: 0,
+            quantity: 1,
+        },
+        Claim {
+            diner: 1,
+            item: 1,
+            quantity: 2,
+        },
+        Claim {
+            diner: 2,
+            item: 1,
+            quantity: 1,
+        },
+    ];
+    let shares = bill.split_by_items(3, &claims).unwrap();
+    assert_adds_up(&bill, &shares);
+    let subtotals: Vec<u64> = shares.iter().map(|share| share.subtotal_cents).collect();
+    assert_eq!(subtotals, [1450, 2350, 450]);
+
+    // A diner who claimed nothing pays nothing.
+    let shares = bill.split_by_items(4, &claims).unwrap();
+    assert_adds_up(&bill, &shares);
+    assert_eq!(shares[3].total_cents(), 0);
+}
+
+#[test]
+fn item_split_rejects_bad_claims() {
+    let bill = bill(&[("Burger", 2, 1450)], 800, 15);
+    let claim = |diner, item, quantity| Claim {
+        diner,
+        item,
+        quantity,
+    };
+    assert_eq!(
+        bill.split_by_items(2, &[claim(2, 0, 2)]),
+        Err(BillingError::UnknownDiner(2))
+    );
+    assert_eq!(
+        bill.split_by_items(2, &[claim(0, 1, 2)]),
+        Err(BillingError::UnknownItem(1))
+    );
+    assert_eq!(
+        bill.split_by_items(2, &[claim(0, 0, 1)]),
+        Err(BillingError::ClaimMismatch {
+            item: 0,
+            ordered: 2,
+            claimed: 1
+        })
+    );
+    assert_eq!(
+        bill.split_by_items(2, &[claim(0, 0, u32::MAX), claim(1, 0, 2)]),
+        Err(BillingError::AmountTooLarge)
+    );
+}
+
+#[test]
+fn allocate_gives_leftover_cents_to_the_largest_remainders() {
+    assert_eq!(allocate(100, &[1, 1, 1]), [34, 33, 33]);
+    assert_eq!(allocate(10, &[1, 2, 3, 4]), [1, 2, 3, 4]);
+    assert_eq!(allocate(7, &[0, 0]), [4, 3]);
+    assert_eq!(allocate(5, &[0, 3]), [0, 5]);
+    assert_eq!(allocate(0, &[2, 5]), [0, 0]);
+    assert_eq!(allocate(3, &[]), Vec::<u64>::new());
+
+    // 101 cents in proportion 1:1:1 is 33.67 each; the two earliest get the extra cent.
+    let parts = allocate(101, &[5, 5, 5]);
+    assert_eq!(parts, [34, 34, 33]);
+
+    let weights = [u64::MAX, u64::MAX, 7];
+    let parts = allocate(u64::MAX, &weights);
+    assert_eq!(
+        parts.iter().map(|&part| part as u128).sum::<u128>(),
+        u64::MAX as u128
+    );
+    for total in [1, 99, 1000, 123_457] {
+        let parts = allocate(total, &[3, 1, 4, 1, 5, 9, 2, 6]);
+        assert_eq!(parts.iter().sum::<u64>(), total);
+    }
+}
+
+#[test]
+fn refuses_amounts_that_do_not_fit() {
+    let huge = [LineItem::new("Caviar", u32::MAX, u64::MAX / 2)];
+    assert_eq!(
+        Bill::new(&huge, Rates::default()),
+        Err(BillingError::AmountTooLarge)
+    );
+    let big = [LineItem::new("Yacht", 1, u64::MAX / 10)];
+    assert_eq!(
+        Bill::new(&big, Rates::default()),
+        Err(BillingError::AmountTooLarge)
+    );
+    assert!(
+        Bill::new(
+            &big,
+            Rates {
+                tax_basis_points: 0,
+                tip_percent: 0
+            }
+        )
+        .is_ok()
+    );
+
+    assert_eq!(huge[0].checked_total_cents(), None);
+    assert_eq!(huge[0].total_cents(), u64::MAX);
+    assert_eq!(
+        serving::take_order(OrderId(1), TableId(1), huge.to_vec()),
+        Err(ServingError::TotalTooLarge)
+    );
+
+    // A bill that cannot be worked out leaves the order unpaid.
+    let mut order = serving::take_order(OrderId(2), TableId(1), big.to_vec()).unwrap();
+    serving::send_to_kitchen(&mut order).unwrap();
+    serving::serve_order(&mut order).unwrap();
+    assert_eq!(
+        serving::take_payment(&mut order, Rates::default()),
+        Err(ServingError::Billing(BillingError::AmountTooLarge))
+    );
+    assert_eq!(order.state(), OrderState::Served);
+    assert_eq!(order.bill(), None);
+}
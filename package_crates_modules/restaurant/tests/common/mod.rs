@@ -0,0 +1,11 @@
+use restaurant::time::Timestamp;
+
+// The given time on June 1st, 2024, in UTC.
+pub fn at(hour: u32, minute: u32) -> Timestamp {
+    on(1, hour, minute)
+}
+
+// The given time on a day of June 2024, in UTC.
+pub fn on(day: u32, hour: u32, minute: u32) -> Timestamp {
+    Timestamp::from_date_time(2024, 6, day, hour, minute).unwrap()
+}
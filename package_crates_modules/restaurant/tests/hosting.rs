@@ -0,0 +1,141 @@
+mod common;
+
+use common::at;
+use restaurant::Restaurant;
+use restaurant::back_of_house::menu::Menu;
+use restaurant::front_of_house::billing::Rates;
+use restaurant::front_of_house::hosting::{HostingError, TableId, Ticket};
+use restaurant::front_of_house::serving::LineItem;
+
+#[test]
+fn rejects_blank_names_and_impossible_parties() {
+    let mut restaurant = Restaurant::new(&[2, 4]);
+    assert_eq!(
+        restaurant.add_to_waitlist("", 2, at(18, 0)),
+        Err(HostingError::BlankName)
+    );
+    assert_eq!(
+        restaurant.add_to_waitlist("  \t", 2, at(18, 0)),
+        Err(HostingError::BlankName)
+    );
+    assert_eq!(
+        restaurant.add_to_waitlist("Ada", 0, at(18, 0)),
+        Err(HostingError::EmptyParty)
+    );
+    assert_eq!(
+        restaurant.add_to_waitlist("Ada", 5, at(18, 0)),
+        Err(HostingError::PartyTooLarge {
+            size: 5,
+            largest_table: 4
+        })
+    );
+    assert_eq!(restaurant.waitlist().count(), 0);
+    // Rejected parties do not use up a ticket.
+    assert_eq!(
+        restaurant.add_to_waitlist("Ada", 2, at(18, 0)),
+        Ok(Ticket(1))
+    );
+}
+
+#[test]
+fn seats_the_waitlist_first_come_first_served() {
+    let mut restaurant = Restaurant::new(&[4, 4, 4]);
+    for name in ["Ada", "Bob", "Cy"] {
+        restaurant.add_to_waitlist(name, 2, at(18, 0)).unwrap();
+    }
+    restaurant.leave_waitlist(Ticket(2)).unwrap();
+    assert_eq!(
+        restaurant.leave_waitlist(Ticket(2)),
+        Err(HostingError::UnknownTicket(Ticket(2)))
+    );
+
+    let seated: Vec<String> = (0..2)
+        .map(|_| restaurant.seat_at_table(at(18, 5)).unwrap().party.name)
+        .collect();
+    assert_eq!(seated, ["Ada", "Cy"]);
+    assert_eq!(
+        restaurant.seat_at_table(at(18, 5)),
+        Err(HostingError::WaitlistEmpty)
+    );
+}
+
+#[test]
+fn picks_the_smallest_free_table_that_fits() {
+    let mut restaurant = Restaurant::new(&[6, 2, 4]);
+    restaurant.add_to_waitlist("Ada", 3, at(18, 0)).unwrap();
+    restaurant.add_to_waitlist("Bob", 2, at(18, 0)).unwrap();
+    restaurant.add_to_waitlist("Cy", 2, at(18, 0)).unwrap();
+    restaurant.add_to_waitlist("Dee", 5, at(18, 0)).unwrap();
+
+    let tables: Vec<TableId> = (0..3)
+        .map(|_| restaurant.seat_at_table(at(18, 5)).unwrap().table)
+        .collect();
+    assert_eq!(tables, [TableId(3), TableId(2), TableId(1)]);
+    // The big table went to a party of two, so the party of five must wait.
+    assert_eq!(
+        restaurant.seat_at_table(at(18, 5)),
+        Err(HostingError::NoTableAvailable {
+            ticket: Ticket(4),
+            size: 5
+        })
+    );
+}
+
+#[test]
+fn keeps_tables_held_for_an_upcoming_booking() {
+    let mut restaurant = Restaurant::new(&[2, 4]);
+    restaurant.book("Ada", 2, at(19, 0), at(12, 0)).unwrap();
+    restaurant.add_to_waitlist("Bob", 2, at(18, 0)).unwrap();
+    restaurant.add_to_waitlist("Cy", 2, at(18, 0)).unwrap();
+
+    // The two-top is held for Ada, so Bob gets the four-top and Cy waits.
+    assert_eq!(
+        restaurant.seat_at_table(at(18, 0)).unwrap().table,
+        TableId(2)
+    );
+    assert!(matches!(
+        restaurant.seat_at_table(at(18, 0)),
+        Err(HostingError::NoTableAvailable { .. })
+    ));
+    assert_eq!(restaurant.table(TableId(1)).unwrap().occupant(), None);
+}
+
+#[test]
+fn free_table_waits_for_every_order_to_be_paid() {
+    let mut restaurant = Restaurant::new(&[4]);
+    restaurant.add_to_waitlist("Ada", 2, at(18, 0)).unwrap();
+    let table = restaurant.seat_at_table(at(18, 0)).unwrap().table;
+    let order = restaurant
+        .take_order(table, vec![LineItem::new("soup", 2, 450)])
+        .unwrap();
+
+    assert_eq!(
+        restaurant.free_table(table, at(19, 0)),
+        Err(HostingError::UnpaidOrders(table))
+    );
+    restaurant
+        .send_to_kitchen(order, &Menu::house(), at(19, 0))
+        .unwrap();
+    restaurant.serve(order).unwrap();
+    assert_eq!(
+        restaurant.free_table(table, at(19, 0)),
+        Err(HostingError::UnpaidOrders(table))
+    );
+    restaurant
+        .take_payment(order, Rates::default(), at(19, 5))
+        .unwrap();
+
+    assert_eq!(
+        restaurant.free_table(table, at(19, 10)).unwrap().name,
+        "Ada"
+    );
+    assert!(restaurant.table(table).unwrap().is_free());
+    assert_eq!(
+        restaurant.free_table(table, at(19, 10)),
+        Err(HostingError::TableNotOccupied(table))
+    );
+    assert_eq!(
+        restaurant.free_table(TableId(9), at(19, 10)),
+        Err(HostingError::UnknownTable(TableId(9)))
+    );
+}
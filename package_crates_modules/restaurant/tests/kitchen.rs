@@ -0,0 +1,204 @@
+use restaurant::Restaurant;
+use restaurant::back_of_house::kitchen::{Kitchen, KitchenError};
+use restaurant::back_of_house::menu::{Dish, Menu, MenuError, Station};
+use restaurant::front_of_house::hosting::TableId;
+use restaurant::front_of_house::serving::{
+    self, LineItem, Order, OrderId, OrderState, ServingError,
+};
+use restaurant::time::Timestamp;
+
+fn minute(minutes: u64) -> Timestamp {
+    Timestamp::from_minutes(minutes)
+}
+
+fn order(id: u32, items: &[(&str, u32)]) -> Order {
+    let menu = Menu::house();
+    let items = items
+        .iter()
+        .map(|&(name, quantity)| menu.line_item(name, quantity).unwrap())
+        .collect();
+    serving::take_order(OrderId(id), TableId(1), items).unwrap()
+}
+
+#[test]
+fn submit_schedules_portions_one_after_another_per_station() {
+    let menu = Menu::house();
+    let mut kitchen = Kitchen::new();
+
+    // Two burgers take 24 minutes on the grill; the fries cook alongside.
+    let mut first = order(1, &[("Burger", 2), ("Fries", 1)]);
+    let ticket = kitchen.submit(&mut first, &menu, minute(10)).unwrap();
+    assert_eq!(
+        (ticket.received_at, ticket.ready_at),
+        (minute(10), minute(34))
+    );
+    assert_eq!(first.state(), OrderState::InKitchen);
+
+    // The steak waits for the grill, the salad does not.
+    let mut second = order(2, &[("Steak", 1), ("Salad", 1)]);
+    assert_eq!(
+        kitchen
+            .submit(&mut second, &menu, minute(12))
+            .unwrap()
+            .ready_at,
+        minute(52)
+    );
+
+    let summary = kitchen.summary(minute(20));
+    assert_eq!(summary.open_tickets, 2);
+    assert_eq!(summary.longest_wait, 32);
+    assert!(summary.station_backlog.contains(&(Station::Grill, 32)));
+    assert!(summary.station_backlog.contains(&(Station::Cold, 0)));
+
+    // An order can only go to the kitchen once.
+    assert!(matches!(
+        kitchen.submit(&mut first, &menu, minute(20)),
+        Err(KitchenError::Serving(_))
+    ));
+}
+
+#[test]
+fn unknown_dishes_leave_the_kitchen_untouched() {
+    let menu = Menu::house();
+    let mut kitchen = Kitchen::new();
+    let mut bad = serving::take_order(
+        OrderId(1),
+        TableId(1),
+        vec![
+            LineItem::new("Burger", 1, 1450),
+            LineItem::new("Lobster", 1, 5000),
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        kitchen.submit(&mut bad, &menu, minute(0)),
+        Err(KitchenError::Menu(MenuError::UnknownDish("Lobster".into())))
+    );
+    assert_eq!(bad.state(), OrderState::Taken);
+    assert_eq!(kitchen, Kitchen::new());
+}
+
+#[test]
+fn quote_does_not_change_the_schedule() {
+    let menu = Menu::house();
+    let mut kitchen = Kitchen::new();
+    kitchen
+        .submit(&mut order(1, &[("Pasta", 3)]), &menu, minute(0))
+        .unwrap();
+
+    let soup = [menu.line_item("soup", 2).unwrap()];
+    assert_eq!(kitchen.quote(&soup, &menu, minute(5)), Ok(35));
+    assert_eq!(kitchen.quote(&soup, &menu, minute(5)), Ok(35));
+    assert_eq!(kitchen.quote(&soup, &menu, minute(40)), Ok(10));
+    let cake = [menu.line_item("cake", 1).unwrap()];
+    assert_eq!(kitchen.quote(&cake, &menu, minute(5)), Ok(3));
+}
+
+#[test]
+fn collect_ready_takes_finished_tickets_in_the_order_they_are_ready() {
+    let menu = Menu::house();
+    let mut kitchen = Kitchen::new();
+    kitchen
+        .submit(&mut order(1, &[("Steak", 1)]), &menu, minute(0))
+        .unwrap();
+    kitchen
+        .submit(&mut order(2, &[("Salad", 1)]), &menu, minute(1))
+        .unwrap();
+    kitchen
+        .submit(&mut order(3, &[("Burger", 1)]), &menu, minute(2))
+        .unwrap();
+
+    assert!(kitchen.collect_ready(minute(4)).is_empty());
+    let ready: Vec<OrderId> = kitchen
+        .collect_ready(minute(20))
+        .iter()
+        .map(|ticket| ticket.order)
+        .collect();
+    assert_eq!(ready, [OrderId(2), OrderId(1)]);
+    assert_eq!(kitchen.ticket(OrderId(3)).unwrap().ready_at, minute(30));
+    assert_eq!(kitchen.tickets().count(), 1);
+    assert_eq!(kitchen.collect_ready(minute(30)).len(), 1);
+    assert_eq!(kitchen.summary(minute(30)).open_tickets, 0);
+}
+
+#[test]
+fn huge_orders_are_refused_instead_of_overflowing() {
+    let mut menu = Menu::new();
+    menu.add(Dish::new("Roast", 1000, 600, Station::Stove, &["beef"]))
+        .unwrap();
+    let mut kitchen = Kitchen::new();
+    let overflow = Err(KitchenError::ScheduleOverflow {
+        dish: "Roast".into(),
+    });
+
+    // A huge order is scheduled without looping per portion.
+    let roasts = [LineItem::new("Roast", u32::MAX, 1000)];
+    assert_eq!(
+        kitchen.quote(&roasts, &menu, minute(0)),
+        Ok(2_576_980_377_000)
+    );
+    assert_eq!(
+        kitchen.quote(&roasts, &menu, Timestamp(u64::MAX - 1_000_000)),
+        overflow
+    );
+
+    // A station busy until the last second cannot take another portion.
+    let roast = [LineItem::new("Roast", 1, 1000)];
+    let mut order = serving::take_order(OrderId(1), TableId(1), roast.to_vec()).unwrap();
+    kitchen
+        .submit(&mut order, &menu, Timestamp(u64::MAX - 36_000))
+        .unwrap();
+    assert_eq!(kitchen.quote(&roast, &menu, minute(0)), overflow);
+}
+
+#[test]
+fn the_restaurant_sends_orders_to_its_kitchen() {
+    let menu = Menu::house();
+    let mut restaurant = Restaurant::new(&[4, 4]);
+    restaurant.add_to_waitlist("Ada", 2, minute(0)).unwrap();
+    restaurant.add_to_waitlist("Bob", 2, minute(0)).unwrap();
+    restaurant.seat_at_table(minute(0)).unwrap();
+    restaurant.seat_at_table(minute(0)).unwrap();
+    let burgers = vec![menu.line_item("burger", 2).unwrap()];
+    assert_eq!(restaurant.quote_wait(&burgers, &menu, minute(5)), Ok(24));
+
+    let first = restaurant.take_order(TableId(1), burgers.clone()).unwrap();
+    let ticket = restaurant.send_to_kitchen(first, &menu, minute(5)).unwrap();
+    assert_eq!(ticket.ready_at, minute(29));
+    assert_eq!(
+        restaurant.order(first).unwrap().state(),
+        OrderState::InKitchen
+    );
+    // The next table's burgers wait for the grill.
+    assert_eq!(restaurant.quote_wait(&burgers, &menu, minute(10)), Ok(43));
+    assert_eq!(restaurant.kitchen().summary(minute(10)).longest_wait, 19);
+
+    // The kitchen is saved with the rest of service.
+    let restored = Restaurant::from_save_str(&restaurant.to_save_string()).unwrap();
+    assert_eq!(restored, restaurant);
+    assert_eq!(restored.kitchen().ticket(first), Some(&ticket));
+
+    let second = restaurant.take_order(TableId(2), burgers).unwrap();
+    assert_eq!(
+        restaurant.send_to_kitchen(first, &menu, minute(10)),
+        Err(KitchenError::Serving(ServingError::IllegalTransition {
+            order: first,
+            from: OrderState::InKitchen,
+            to: OrderState::InKitchen,
+        }))
+    );
+    assert_eq!(
+        restaurant.send_to_kitchen(OrderId(9), &menu, minute(10)),
+        Err(KitchenError::Serving(ServingError::UnknownOrder(OrderId(
+            9
+        ))))
+    );
+    restaurant
+        .send_to_kitchen(second, &menu, minute(10))
+        .unwrap();
+
+    // Serving an order takes its ticket off the queue.
+    restaurant.serve(first).unwrap();
+    assert_eq!(restaurant.kitchen().ticket(first), None);
+    assert_eq!(restaurant.kitchen().tickets().count(), 1);
+}
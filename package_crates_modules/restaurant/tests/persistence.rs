@@ -0,0 +1,263 @@
+mod common;
+
+use common::at;
+use restaurant::Restaurant;
+use restaurant::back_of_house::menu::Menu;
+use restaurant::front_of_house::billing::Rates;
+use restaurant::front_of_house::hosting::{TableId, Ticket};
+use restaurant::front_of_house::reservations::ReservationState;
+use restaurant::front_of_house::serving::{LineItem, OrderId, OrderState};
+use restaurant::persistence::{CURRENT_VERSION, PersistError};
+use restaurant::service_log::EventKind;
+
+fn round_trip(restaurant: &Restaurant) -> Restaurant {
+    Restaurant::from_save_str(&restaurant.to_save_string()).unwrap()
+}
+
+fn parse_error(text: &str) -> (usize, String) {
+    match Restaurant::from_save_str(text) {
+        Err(PersistError::Parse { line, message }) => (line, message),
+        other => panic!("expected a parse error, got {other:?}"),
+    }
+}
+
+#[test]
+fn a_service_in_progress_survives_a_restart() {
+    let mut restaurant = Restaurant::new(&[2, 4, 6]);
+    restaurant.add_to_waitlist("Ada", 4, at(18, 0)).unwrap();
+    restaurant.add_to_waitlist("Bob", 2, at(18, 1)).unwrap();
+    restaurant.seat_at_table(at(18, 5)).unwrap();
+    restaurant.seat_at_table(at(18, 6)).unwrap();
+    restaurant.add_to_waitlist("Cy", 6, at(18, 7)).unwrap();
+    restaurant.add_to_waitlist("Dee", 2, at(18, 8)).unwrap();
+    let booking = restaurant.book("Eve", 6, at(21, 0), at(18, 0)).unwrap();
+
+    let paid = restaurant
+        .take_order(
+            TableId(2),
+            vec![
+                LineItem::new("Burger", 2, 1450),
+                LineItem::new("Soup", 1, 650),
+            ],
+        )
+        .unwrap();
+    let menu = Menu::house();
+    restaurant.send_to_kitchen(paid, &menu, at(18, 10)).unwrap();
+    restaurant.serve(paid).unwrap();
+    let rates = Rates {
+        tax_basis_points: 825,
+        tip_percent: 18,
+    };
+    restaurant.take_payment(paid, rates, at(19, 0)).unwrap();
+    let open = restaurant
+        .take_order(TableId(1), vec![LineItem::new("Fries", 3, 450)])
+        .unwrap();
+    restaurant.send_to_kitchen(open, &menu, at(19, 5)).unwrap();
+    restaurant.free_table(TableId(2), at(19, 10)).unwrap();
+
+    let mut restored = round_trip(&restaurant);
+    assert_eq!(restored, restaurant);
+    assert_eq!(restored.to_save_string(), restaurant.to_save_string());
+    assert_eq!(restored.order(paid).unwrap().bill().unwrap().rates, rates);
+    assert_eq!(restored.order(open).unwrap().state(), OrderState::InKitchen);
+    assert_eq!(restored.log().len(), restaurant.log().len());
+
+    // The counters carry on where they stopped.
+    assert_eq!(
+        restored.add_to_waitlist("Fay", 2, at(19, 20)),
+        Ok(Ticket(5))
+    );
+    let next_order = restored
+        .take_order(TableId(1), vec![LineItem::new("Cake", 1, 700)])
+        .unwrap();
+    assert_eq!(next_order, OrderId(3));
+    let next_booking = restored.book("Gus", 2, at(22, 0), at(19, 20)).unwrap();
+    assert_eq!(next_booking.0, booking.0 + 1);
+}
+
+#[test]
+fn names_are_saved_exactly() {
+    let names = [
+        "  Ada  ",
+        "Bob \"the Builder\"",
+        "C:\\Users\\cy",
+        "Dee\nand\r\nfriends",
+        "\"",
+        "Zoë ✓",
+    ];
+    let mut restaurant = Restaurant::new(&[4, 4]);
+    for name in names {
+        restaurant.add_to_waitlist(name, 2, at(18, 0)).unwrap();
+    }
+    restaurant.seat_at_table(at(18, 0)).unwrap();
+    restaurant.book(" Eve ", 2, at(21, 0), at(18, 0)).unwrap();
+    let items = vec![
+        LineItem::new("", 1, 100),
+        LineItem::new(" Soup of the day ", 1, 650),
+    ];
+    restaurant.take_order(TableId(1), items.clone()).unwrap();
+
+    let text = restaurant.to_save_string();
+    assert_eq!(text.lines().next(), Some("restaurant-save 5"));
+    assert!(text.contains("seated 1 1 2 \"  Ada  \"\n"));
+    assert!(text.contains("waiting 2 2 \"Bob \\\"the Builder\\\"\"\n"));
+    assert!(text.contains("\"Dee\\nand\\r\\nfriends\""));
+
+    let restored = round_trip(&restaurant);
+    assert_eq!(restored, restaurant);
+    let waiting: Vec<&str> = restored
+        .waitlist()
+        .map(|party| party.name.as_str())
+        .collect();
+    assert_eq!(waiting, &names[1..]);
+    assert_eq!(restored.reservations()[0].name, " Eve ");
+    assert_eq!(restored.orders()[0].items(), items);
+}
+
+#[test]
+fn loads_a_version_1_file() {
+    let text = "\
+restaurant-save 1
+counters 5 3
+table 1 2
+table 2 4
+seated 2 3 4 Ada Lovelace
+waiting 4 2  Bob
+order 1 2 paid 825 15
+item 1 2 1450 Burger
+item 1 1 650 Soup
+order 2 2 served
+item 2 1 450 Fries
+";
+    let mut restaurant = Restaurant::from_save_str(text).unwrap();
+    assert_eq!(restaurant.tables().len(), 2);
+    let party = restaurant.table(TableId(2)).unwrap().occupant().unwrap();
+    assert_eq!((party.ticket, party.size), (Ticket(3), 4));
+    assert_eq!(party.name, "Ada Lovelace");
+    // Up to version 3 the name is everything after the separating space.
+    assert_eq!(restaurant.waitlist().next().unwrap().name, " Bob");
+
+    let bill = restaurant.order(OrderId(1)).unwrap().bill().unwrap();
+    assert_eq!(bill.subtotal_cents, 3550);
+    assert_eq!((bill.tax_cents, bill.tip_cents), (293, 533));
+    assert_eq!(
+        restaurant.order(OrderId(2)).unwrap().state(),
+        OrderState::Served
+    );
+    assert!(restaurant.log().is_empty());
+    assert!(restaurant.reservations().is_empty());
+
+    // It is saved again in the current format and numbering carries on.
+    let saved = restaurant.to_save_string();
+    assert!(saved.starts_with(&format!("restaurant-save {CURRENT_VERSION}\n")));
+    assert!(saved.contains("waiting 4 2 \" Bob\"\n"));
+    assert_eq!(round_trip(&restaurant), restaurant);
+    assert_eq!(
+        restaurant.add_to_waitlist("Cy", 2, at(19, 0)),
+        Ok(Ticket(5))
+    );
+    let booking = restaurant.book("Dee", 2, at(21, 0), at(19, 0)).unwrap();
+    assert_eq!(booking.0, 1);
+}
+
+#[test]
+fn loads_version_2_and_3_files() {
+    let version_2 = "\
+restaurant-save 2
+counters 3 2
+table 1 4
+seated 1 1 2 Ada
+waiting 2 3 Bob
+order 1 1 taken
+item 1 1 800 Salad
+event 1717264800 joined 1 2
+event 1717264920 joined 2 3
+event 1717265100 seated 1 1
+";
+    let restaurant = Restaurant::from_save_str(version_2).unwrap();
+    assert_eq!(restaurant.log().len(), 3);
+    assert_eq!(restaurant.log()[0].at, at(18, 0));
+    assert_eq!(
+        restaurant.log()[2].kind,
+        EventKind::Seated {
+            ticket: Ticket(1),
+            table: TableId(1)
+        }
+    );
+    assert_eq!(restaurant.orders()[0].items()[0].name, "Salad");
+    assert_eq!(round_trip(&restaurant), restaurant);
+
+    let version_3 = "\
+restaurant-save 3
+counters 4 1 3
+table 1 4
+waiting 3 2 Cy
+reservation 1 1 1717268400 4 arrived 2 Ada Lovelace
+reservation 2 1 1717275600 2 cancelled  Bob
+";
+    let restaurant = Restaurant::from_save_str(version_3).unwrap();
+    let reservations = restaurant.reservations();
+    assert_eq!(reservations[0].name, "Ada Lovelace");
+    assert_eq!(reservations[0].start, at(19, 0));
+    assert_eq!(
+        reservations[0].state(),
+        ReservationState::Arrived(Ticket(2))
+    );
+    assert_eq!(reservations[1].name, " Bob");
+    assert_eq!(round_trip(&restaurant), restaurant);
+}
+
+#[test]
+fn blank_names_written_by_older_versions_still_load() {
+    // Version 3 wrote an empty name as a trailing space.
+    let text = "restaurant-save 3\ncounters 2 1 1\ntable 1 2\nwaiting 1 2 \n";
+    let restaurant = Restaurant::from_save_str(text).unwrap();
+    assert_eq!(restaurant.waitlist().next().unwrap().name, "");
+    assert_eq!(round_trip(&restaurant), restaurant);
+}
+
+#[test]
+fn reports_what_is_wrong_with_a_bad_file() {
+    assert!(matches!(
+        Restaurant::from_save_str("hello\n"),
+        Err(PersistError::MissingHeader)
+    ));
+    assert!(matches!(
+        Restaurant::from_save_str("restaurant-save 99\n"),
+        Err(PersistError::UnsupportedVersion(99))
+    ));
+
+    let header = "restaurant-save 4\ncounters 1 1 1\ntable 1 2\n";
+    assert_eq!(
+        parse_error(&format!("{header}waiting 1 2 Ada\n")),
+        (4, "missing party name".to_string())
+    );
+    assert_eq!(
+        parse_error(&format!("{header}waiting 1 2 \"Ada\n")),
+        (4, "unterminated party name".to_string())
+    );
+    assert_eq!(
+        parse_error(&format!("{header}waiting 1 2 \"Ada\" Lovelace\n")),
+        (4, "unexpected text after party name".to_string())
+    );
+    assert_eq!(
+        parse_error(&format!("{header}waiting 1 2 \"A\\da\"\n")),
+        (4, "invalid escape '\\d' in party name".to_string())
+    );
+    assert_eq!(
+        parse_error(&format!("{header}seated 3 1 2 \"Ada\"\n")),
+        (4, "party seated at unknown table T3".to_string())
+    );
+    assert_eq!(
+        parse_error(&format!("{header}order 1 1 eaten\n")),
+        (4, "unknown order state 'eaten'".to_string())
+    );
+    assert_eq!(
+        parse_error(&format!("{header}ticket 1 60 120\n")),
+        (4, "kitchen ticket for unknown order O1".to_string())
+    );
+    assert_eq!(
+        parse_error(&format!("{header}station oven 120\n")),
+        (4, "unknown station 'oven'".to_string())
+    );
+}
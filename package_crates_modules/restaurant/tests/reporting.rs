@@ -0,0 +1,252 @@
+mod common;
+
+use common::{at, on};
+use restaurant::Restaurant;
+use restaurant::back_of_house::menu::{Dish, Menu, Station};
+use restaurant::front_of_house::billing::Rates;
+use restaurant::front_of_house::hosting::TableId;
+use restaurant::front_of_house::serving::LineItem;
+use restaurant::reporting::{DayReport, ReportPeriod};
+use restaurant::time::Timestamp;
+
+// The house menu and the catch of the day.
+fn menu() -> Menu {
+    let mut menu = Menu::house();
+    let fish = Dish::new("Fish, \"fresh\"", 1200, 8, Station::Stove, &["cod"]);
+    menu.add(fish).unwrap();
+    menu
+}
+
+fn serve_and_pay(
+    restaurant: &mut Restaurant,
+    table: TableId,
+    items: Vec<LineItem>,
+    paid: Timestamp,
+) {
+    let order = restaurant.take_order(table, items).unwrap();
+    restaurant.send_to_kitchen(order, &menu(), paid).unwrap();
+    restaurant.serve(order).unwrap();
+    restaurant
+        .take_payment(order, Rates::default(), paid)
+        .unwrap();
+}
+
+// Two parties on June 1st, and one that joins just before midnight and is
+// seated on June 2nd.
+fn two_days_of_service() -> Restaurant {
+    let mut restaurant = Restaurant::new(&[2, 4]);
+    restaurant.add_to_waitlist("Ada", 4, at(17, 50)).unwrap();
+    restaurant.seat_at_table(at(18, 0)).unwrap();
+    restaurant.add_to_waitlist("Bob", 2, at(18, 5)).unwrap();
+    restaurant.seat_at_table(at(18, 20)).unwrap();
+    serve_and_pay(
+        &mut restaurant,
+        TableId(2),
+        vec![
+            LineItem::new("Burger", 2, 1450),
+            LineItem::new("Fries", 1, 450),
+        ],
+        at(19, 10),
+    );
+    restaurant.free_table(TableId(2), at(19, 15)).unwrap();
+    serve_and_pay(
+        &mut restaurant,
+        TableId(1),
+        vec![
+            LineItem::new("Soup", 1, 650),
+            LineItem::new("Fries", 2, 450),
+        ],
+        at(20, 30),
+    );
+    restaurant.free_table(TableId(1), at(20, 40)).unwrap();
+
+    restaurant
+        .add_to_waitlist("Cy, Jr.", 2, at(23, 50))
+        .unwrap();
+    restaurant.seat_at_table(on(2, 0, 10)).unwrap();
+    serve_and_pay(
+        &mut restaurant,
+        TableId(1),
+        vec![LineItem::new("Cake", 1, 700)],
+        on(2, 0, 40),
+    );
+    restaurant.free_table(TableId(1), on(2, 0, 45)).unwrap();
+    restaurant
+}
+
+fn day(day: u32, utc_offset_minutes: i32) -> ReportPeriod {
+    ReportPeriod::day(2024, 6, day, utc_offset_minutes).unwrap()
+}
+
+#[test]
+fn periods_run_from_local_midnight_to_midnight() {
+    let utc = day(1, 0);
+    assert_eq!((utc.start, utc.end), (at(0, 0), on(2, 0, 0)));
+    assert!(utc.contains(at(23, 59)));
+    assert!(!utc.contains(on(2, 0, 0)));
+    assert_eq!(utc.hour_of_day(at(19, 10)), 19);
+
+    let new_york = day(1, -240);
+    assert_eq!((new_york.start, new_york.end), (at(4, 0), on(2, 4, 0)));
+    assert_eq!(new_york.hour_of_day(on(2, 0, 40)), 20);
+    let tokyo = day(1, 540);
+    assert_eq!(
+        tokyo.start,
+        Timestamp::from_date_time(2024, 5, 31, 15, 0).unwrap()
+    );
+    assert_eq!(tokyo.hour_of_day(at(15, 30)), 0);
+
+    assert_eq!(ReportPeriod::day(2024, 2, 30, 0), None);
+    assert_eq!(ReportPeriod::day(1970, 1, 1, 60), None);
+}
+
+#[test]
+fn periods_near_the_last_timestamp_do_not_overflow() {
+    let last = Timestamp(u64::MAX);
+    // The last day that starts before the last Timestamp does not end before it.
+    // It is 07:00 UTC on its day.
+    let (year, month, last_day) = last.date();
+    assert_eq!(day(1, 0).hour_of_day(last), 7);
+    assert_eq!(ReportPeriod::day(year, month, last_day, 0), None);
+    // Nearly a day east of UTC the same date ends before the last Timestamp.
+    let east = ReportPeriod::day(year, month, last_day, 24 * 60 - 1).unwrap();
+    assert!(east.contains(Timestamp(east.end.0 - 1)));
+    assert_eq!(east.hour_of_day(last), 6);
+    assert_eq!(day(1, -60).hour_of_day(last), 6);
+
+    assert_eq!(ReportPeriod::day(i64::MAX, 12, 31, 0), None);
+    assert_eq!(ReportPeriod::day(i64::MIN, 1, 1, 0), None);
+    assert_eq!(Timestamp::from_date_time(i64::MAX, 1, 1, 0, 0), None);
+}
+
+#[test]
+fn build_counts_only_what_happened_in_the_period() {
+    let restaurant = two_days_of_service();
+
+    let first = DayReport::build(&restaurant, day(1, 0));
+    assert_eq!(first.parties_seated, 2);
+    assert_eq!(first.average_party_size, Some(3.0));
+    assert_eq!(first.average_wait_minutes, Some(12.5));
+    let hours: Vec<(u8, u32, u64, u64, u64)> = first
+        .revenue_by_hour
+        .iter()
+        .map(|hour| {
+            (
+                hour.hour,
+                hour.orders,
+                hour.revenue_cents,
+                hour.tax_cents,
+                hour.tip_cents,
+            )
+        })
+        .collect();
+    assert_eq!(hours, [(19, 1, 3350, 268, 503), (20, 1, 1550, 124, 233)]);
+    assert_eq!(first.total_revenue_cents(), 4900);
+    let turnover: Vec<(u32, Option<f64>)> = first
+        .turnover
+        .iter()
+        .map(|table| (table.parties, table.average_minutes_seated))
+        .collect();
+    assert_eq!(turnover, [(1, Some(140.0)), (1, Some(75.0))]);
+    let sellers: Vec<(&str, u32, u64)> = first
+        .best_sellers
+        .iter()
+        .map(|dish| (dish.name.as_str(), dish.quantity, dish.revenue_cents))
+        .collect();
+    assert_eq!(
+        sellers,
+        [("Fries", 3, 1350), ("Burger", 2, 2900), ("Soup", 1, 650)]
+    );
+
+    // Cy joined on the 1st but was seated on the 2nd: the whole wait counts.
+    let second = DayReport::build(&restaurant, day(2, 0));
+    assert_eq!(second.parties_seated, 1);
+    assert_eq!(second.average_wait_minutes, Some(20.0));
+    assert_eq!(second.total_revenue_cents(), 700);
+    assert_eq!(second.turnover[1].parties, 0);
+    assert_eq!(second.turnover[1].average_minutes_seated, None);
+
+    let quiet = DayReport::build(&restaurant, day(3, 0));
+    assert_eq!(quiet.parties_seated, 0);
+    assert_eq!(quiet.average_party_size, None);
+    assert!(quiet.revenue_by_hour.is_empty());
+    assert!(quiet.best_sellers.is_empty());
+}
+
+#[test]
+fn hours_are_bucketed_in_local_time() {
+    let restaurant = two_days_of_service();
+    // June 1st in UTC-5 runs until 05:00 UTC on the 2nd, so Cy's table is in it.
+    let report = DayReport::build(&restaurant, day(1, -300));
+    assert_eq!(report.parties_seated, 3);
+    let hours: Vec<(u8, u64)> = report
+        .revenue_by_hour
+        .iter()
+        .map(|hour| (hour.hour, hour.revenue_cents))
+        .collect();
+    assert_eq!(hours, [(14, 3350), (15, 1550), (19, 700)]);
+}
+
+#[test]
+fn to_text_lays_out_every_section() {
+    let report = DayReport::build(&two_days_of_service(), day(2, 0));
+    assert_eq!(
+        report.to_text(),
+        "\
+End of day report
+
+Parties seated:      1
+Average party size:  2.0
+Average wait:        20.0 min
+Revenue:             $7.00
+
+Revenue by hour
+Hour   Orders    Revenue       Tax      Tips
+00:00       1      $7.00     $0.56     $1.05
+
+Table turnover
+Table  Seats  Parties  Avg minutes
+T1         2        1         35.0
+T2         4        0            -
+
+Best sellers
+Dish                  Sold    Revenue
+Cake                     1      $7.00
+"
+    );
+}
+
+#[test]
+fn to_csv_writes_one_row_per_value() {
+    let mut restaurant = two_days_of_service();
+    restaurant.add_to_waitlist("Dee", 2, on(2, 12, 0)).unwrap();
+    restaurant.seat_at_table(on(2, 12, 0)).unwrap();
+    serve_and_pay(
+        &mut restaurant,
+        TableId(1),
+        vec![LineItem::new("Fish, \"fresh\"", 1, 1200)],
+        on(2, 12, 30),
+    );
+
+    let report = DayReport::build(&restaurant, day(2, 0));
+    assert_eq!(
+        report.to_csv(),
+        "\
+section,name,count,value
+summary,parties_seated,2,
+summary,average_party_size,,2.0
+summary,average_wait_minutes,,10.0
+summary,revenue_cents,,1900
+revenue_by_hour,00:00,1,700
+tax_by_hour,00:00,1,56
+tips_by_hour,00:00,1,105
+revenue_by_hour,12:00,1,1200
+tax_by_hour,12:00,1,96
+tips_by_hour,12:00,1,180
+table_turnover,T1,2,35.0
+table_turnover,T2,0,
+best_sellers,\"Fish, \"\"fresh\"\"\",1,1200
+best_sellers,Cake,1,700
+"
+    );
+}
@@ -0,0 +1,174 @@
+mod common;
+
+use common::at;
+use restaurant::Restaurant;
+use restaurant::front_of_house::hosting::{HostingError, TableId};
+use restaurant::front_of_house::reservations::{
+    GRACE_MINUTES, ReservationError, ReservationState, SLOT_MINUTES,
+};
+use restaurant::time::Timestamp;
+
+#[test]
+fn books_the_smallest_table_that_fits() {
+    let mut restaurant = Restaurant::new(&[2, 4, 6]);
+    let id = restaurant.book("Ada", 3, at(19, 0), at(12, 0)).unwrap();
+    assert_eq!(restaurant.reservation(id).unwrap().table, TableId(2));
+}
+
+#[test]
+fn rejects_double_booking_of_a_table() {
+    let mut restaurant = Restaurant::new(&[4]);
+    let first = restaurant
+        .book_table(TableId(1), "Ada", 4, at(19, 0), at(12, 0))
+        .unwrap();
+    assert_eq!(
+        restaurant.book_table(TableId(1), "Bob", 2, at(20, 0), at(12, 0)),
+        Err(ReservationError::TableBooked {
+            table: TableId(1),
+            by: first
+        })
+    );
+    // A slot that starts when the first one ends does not overlap.
+    let later = at(19, 0).plus_minutes(SLOT_MINUTES);
+    assert!(
+        restaurant
+            .book_table(TableId(1), "Bob", 2, later, at(12, 0))
+            .is_ok()
+    );
+    assert_eq!(
+        restaurant.book("Cy", 2, at(18, 0), at(12, 0)),
+        Err(ReservationError::NoTableForSlot {
+            size: 2,
+            start: at(18, 0)
+        })
+    );
+}
+
+#[test]
+fn cancelled_bookings_free_their_slot() {
+    let mut restaurant = Restaurant::new(&[4]);
+    let id = restaurant.book("Ada", 4, at(19, 0), at(12, 0)).unwrap();
+    restaurant.cancel_reservation(id).unwrap();
+    assert!(restaurant.book("Bob", 4, at(19, 30), at(12, 0)).is_ok());
+}
+
+#[test]
+fn rejects_blank_names_and_empty_parties() {
+    let mut restaurant = Restaurant::new(&[4]);
+    assert_eq!(
+        restaurant.book("", 2, at(19, 0), at(12, 0)),
+        Err(ReservationError::BlankName)
+    );
+    assert_eq!(
+        restaurant.book_table(TableId(1), "   ", 2, at(19, 0), at(12, 0)),
+        Err(ReservationError::BlankName)
+    );
+    assert_eq!(
+        restaurant.book("Ada", 0, at(19, 0), at(12, 0)),
+        Err(ReservationError::EmptyParty)
+    );
+    assert!(restaurant.reservations().is_empty());
+}
+
+#[test]
+fn rejects_slots_in_the_past() {
+    let mut restaurant = Restaurant::new(&[4]);
+    assert_eq!(
+        restaurant.book("Ada", 2, at(11, 0), at(12, 0)),
+        Err(ReservationError::SlotInPast { start: at(11, 0) })
+    );
+}
+
+#[test]
+fn releases_no_shows_after_the_grace_period() {
+    let mut restaurant = Restaurant::new(&[4]);
+    let id = restaurant.book("Ada", 4, at(19, 0), at(12, 0)).unwrap();
+
+    let on_time = at(19, 0).plus_minutes(GRACE_MINUTES);
+    assert!(restaurant.release_no_shows(on_time).is_empty());
+
+    let released = restaurant.release_no_shows(on_time.plus_minutes(1));
+    assert_eq!(released.len(), 1);
+    assert_eq!(
+        restaurant.reservation(id).unwrap().state(),
+        ReservationState::NoShow
+    );
+    assert!(matches!(
+        restaurant.check_in(id, on_time.plus_minutes(2)),
+        Err(ReservationError::NotBooked { .. })
+    ));
+}
+
+#[test]
+fn seats_checked_in_bookings_before_walk_ins() {
+    let mut restaurant = Restaurant::new(&[4]);
+    let id = restaurant.book("Ada", 4, at(19, 0), at(12, 0)).unwrap();
+    restaurant.add_to_waitlist("Bob", 2, at(18, 50)).unwrap();
+    let ticket = restaurant.check_in(id, at(18, 55)).unwrap();
+
+    let seating = restaurant.seat_at_table(at(19, 0)).unwrap();
+    assert_eq!(seating.reservation, Some(id));
+    assert_eq!(seating.party.ticket, ticket);
+    assert_eq!(
+        restaurant.reservation(id).unwrap().state(),
+        ReservationState::Seated(ticket)
+    );
+    assert_eq!(restaurant.waitlist().count(), 1);
+}
+
+#[test]
+fn walk_ins_do_not_take_a_table_held_for_a_booking() {
+    let mut restaurant = Restaurant::new(&[4]);
+    restaurant.book("Ada", 4, at(19, 0), at(12, 0)).unwrap();
+    restaurant.add_to_waitlist("Bob", 2, at(18, 0)).unwrap();
+
+    assert!(matches!(
+        restaurant.seat_at_table(at(18, 0)),
+        Err(HostingError::NoTableAvailable { .. })
+    ));
+    // Once the booking is a no-show the table goes back to walk-ins.
+    let late = at(19, 0).plus_minutes(GRACE_MINUTES + 1);
+    let seating = restaurant.seat_at_table(late).unwrap();
+    assert_eq!(seating.party.name, "Bob");
+    assert_eq!(seating.reservation, None);
+}
+
+#[test]
+fn reservations_survive_a_save_and_restore() {
+    let mut restaurant = Restaurant::new(&[2, 4]);
+    let booked = restaurant
+        .book("Ada Lovelace", 2, at(19, 0), at(12, 0))
+        .unwrap();
+    let arrived = restaurant.book("Bob", 4, at(19, 0), at(12, 0)).unwrap();
+    restaurant.check_in(arrived, at(18, 58)).unwrap();
+
+    let mut restored = Restaurant::from_save_str(&restaurant.to_save_string()).unwrap();
+    assert_eq!(restored, restaurant);
+    assert_eq!(restored.reservation(booked).unwrap().name, "Ada Lovelace");
+    let next = restored.book("Cy", 2, at(21, 0), at(12, 0)).unwrap();
+    assert_eq!(next.0, 3);
+}
+
+#[test]
+fn slots_at_the_end_of_time_do_not_overflow() {
+    let last = Timestamp(u64::MAX);
+    assert_eq!(Timestamp(u64::MAX - 60).plus_minutes(SLOT_MINUTES), last);
+    assert_eq!(Timestamp::from_minutes(u64::MAX), last);
+
+    // A save file can hold any start time.
+    let text = format!(
+        "restaurant-save 4\ncounters 1 1 2\ntable 1 2\nreservation 1 1 {} 2 booked \"Ada\"\n",
+        u64::MAX - 60
+    );
+    let mut restaurant = Restaurant::from_save_str(&text).unwrap();
+    assert_eq!(restaurant.reservations()[0].end(), last);
+    assert!(restaurant.release_no_shows(at(19, 0)).is_empty());
+    assert!(restaurant.release_no_shows(last).is_empty());
+    restaurant.book("Bob", 2, at(19, 0), at(12, 0)).unwrap();
+    restaurant.add_to_waitlist("Cy", 2, last).unwrap();
+    // The table is held for both bookings.
+    assert!(matches!(
+        restaurant.seat_at_table(last),
+        Err(HostingError::NoTableAvailable { .. })
+    ));
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use common::at;
+use restaurant::Restaurant;
+use restaurant::back_of_house::kitchen::KitchenError;
+use restaurant::back_of_house::menu::Menu;
+use restaurant::front_of_house::billing::Rates;
+use restaurant::front_of_house::hosting::TableId;
+use restaurant::front_of_house::serving::{LineItem, OrderId, OrderState, ServingError};
+use restaurant::service_log::EventKind;
+
+// A restaurant with a party seated at T1 and T2 free.
+fn seated() -> Restaurant {
+    let mut restaurant = Restaurant::new(&[4, 4]);
+    restaurant.add_to_waitlist("Ada", 2, at(18, 0)).unwrap();
+    restaurant.seat_at_table(at(18, 0)).unwrap();
+    restaurant
+}
+
+// Sends an order to the kitchen and keeps only what serving had to say.
+fn send(restaurant: &mut Restaurant, id: OrderId) -> Result<(), ServingError> {
+    match restaurant.send_to_kitchen(id, &Menu::house(), at(18, 10)) {
+        Ok(_) => Ok(()),
+        Err(KitchenError::Serving(err)) => Err(err),
+        Err(err) => panic!("unexpected kitchen error: {err}"),
+    }
+}
+
+fn illegal(order: u32, from: OrderState, to: OrderState) -> Result<(), ServingError> {
+    Err(ServingError::IllegalTransition {
+        order: OrderId(order),
+        from,
+        to,
+    })
+}
+
+#[test]
+fn an_order_goes_through_every_state_once_and_in_order() {
+    let mut restaurant = seated();
+    let id = restaurant
+        .take_order(
+            TableId(1),
+            vec![
+                LineItem::new("Burger", 2, 1450),
+                LineItem::new("Fries", 1, 450),
+            ],
+        )
+        .unwrap();
+    let rates = Rates::default();
+    let order = restaurant.order(id).unwrap();
+    assert_eq!((order.id(), order.table()), (OrderId(1), TableId(1)));
+    assert_eq!(order.items().len(), 2);
+    assert_eq!(order.total_cents(), 3350);
+    assert_eq!(order.state(), OrderState::Taken);
+
+    assert_eq!(
+        restaurant.serve(id),
+        illegal(1, OrderState::Taken, OrderState::Served)
+    );
+    assert_eq!(
+        restaurant.take_payment(id, rates, at(18, 30)).map(|_| ()),
+        illegal(1, OrderState::Taken, OrderState::Paid)
+    );
+
+    send(&mut restaurant, id).unwrap();
+    assert_eq!(restaurant.order(id).unwrap().state(), OrderState::InKitchen);
+    assert_eq!(
+        send(&mut restaurant, id),
+        illegal(1, OrderState::InKitchen, OrderState::InKitchen)
+    );
+    assert_eq!(
+        restaurant.take_payment(id, rates, at(18, 30)).map(|_| ()),
+        illegal(1, OrderState::InKitchen, OrderState::Paid)
+    );
+
+    restaurant.serve(id).unwrap();
+    assert_eq!(restaurant.order(id).unwrap().state(), OrderState::Served);
+    assert_eq!(
+        restaurant.serve(id),
+        illegal(1, OrderState::Served, OrderState::Served)
+    );
+    assert_eq!(
+        send(&mut restaurant, id),
+        illegal(1, OrderState::Served, OrderState::InKitchen)
+    );
+    // Nothing so far was paid, so nothing was logged as paid.
+    assert!(
+        !restaurant
+            .log()
+            .iter()
+            .any(|event| matches!(event.kind, EventKind::Paid { .. }))
+    );
+
+    let bill = restaurant.take_payment(id, rates, at(19, 0)).unwrap();
+    let order = restaurant.order(id).unwrap();
+    assert_eq!(order.state(), OrderState::Paid);
+    assert_eq!(order.bill(), Some(&bill));
+    assert!(!order.is_open());
+    assert_eq!(restaurant.open_orders().count(), 0);
+
+    assert_eq!(
+        restaurant.take_payment(id, rates, at(19, 5)).map(|_| ()),
+        illegal(1, OrderState::Paid, OrderState::Paid)
+    );
+    assert_eq!(
+        send(&mut restaurant, id),
+        illegal(1, OrderState::Paid, OrderState::InKitchen)
+    );
+    assert_eq!(
+        restaurant.serve(id),
+        illegal(1, OrderState::Paid, OrderState::Served)
+    );
+    assert_eq!(restaurant.order(id).unwrap().bill(), Some(&bill));
+}
+
+#[test]
+fn take_order_checks_the_table_and_the_items() {
+    let mut restaurant = seated();
+    assert_eq!(
+        restaurant.take_order(TableId(1), Vec::new()),
+        Err(ServingError::EmptyOrder)
+    );
+    assert_eq!(
+        restaurant.take_order(
+            TableId(1),
+            vec![
+                LineItem::new("Soup", 1, 650),
+                LineItem::new("Fries", 0, 450),
+            ],
+        ),
+        Err(ServingError::ZeroQuantity("Fries".into()))
+    );
+    assert_eq!(
+        restaurant.take_order(TableId(1), vec![LineItem::new("Caviar", 2, u64::MAX)]),
+        Err(ServingError::TotalTooLarge)
+    );
+    let soup = vec![LineItem::new("Soup", 1, 650)];
+    assert_eq!(
+        restaurant.take_order(TableId(2), soup.clone()),
+        Err(ServingError::TableNotSeated(TableId(2)))
+    );
+    assert_eq!(
+        restaurant.take_order(TableId(9), soup.clone()),
+        Err(ServingError::TableNotSeated(TableId(9)))
+    );
+
+    // Refused orders do not use up an order number.
+    assert!(restaurant.orders().is_empty());
+    assert_eq!(restaurant.take_order(TableId(1), soup), Ok(OrderId(1)));
+}
+
+#[test]
+fn unknown_orders_are_reported() {
+    let mut restaurant = seated();
+    let missing = Err(ServingError::UnknownOrder(OrderId(7)));
+    assert_eq!(restaurant.order(OrderId(7)).map(|_| ()), missing);
+    assert_eq!(send(&mut restaurant, OrderId(7)), missing);
+    assert_eq!(restaurant.serve(OrderId(7)), missing);
+    assert_eq!(
+        restaurant
+            .take_payment(OrderId(7), Rates::default(), at(19, 0))
+            .map(|_| ()),
+        missing
+    );
+    assert_eq!(
+        ServingError::UnknownOrder(OrderId(7)).to_string(),
+        "there is no order O7"
+    );
+    assert_eq!(
+        illegal(3, OrderState::Served, OrderState::Served)
+            .unwrap_err()
+            .to_string(),
+        "order O3 is served and cannot become served"
+    );
+}
@@ -71,8 +71,530 @@
 
 // Both relative and absolute are followed by one or more
 // identifiers separated by double colons(::).
-myrestaurant::front_of_house::hosting::add_to_waitlist();
+// For example, this binary reaches the library through absolute paths such as
+// my_restaurant::front_of_house::hosting::Restaurant, and brings them into scope with use.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use my_restaurant::Restaurant;
+use my_restaurant::back_of_house::kitchen::KitchenError;
+use my_restaurant::back_of_house::menu::{Menu, MenuError};
+use my_restaurant::front_of_house::billing::{BillingError, Rates, format_cents};
+use my_restaurant::front_of_house::hosting::{HostingError, TableId, Ticket};
+use my_restaurant::front_of_house::reservations::{ReservationError, ReservationId};
+use my_restaurant::front_of_house::serving::{LineItem, OrderId, ServingError};
+use my_restaurant::persistence::PersistError;
+use my_restaurant::reporting::{DayReport, ReportPeriod};
+use my_restaurant::time::Timestamp;
+
+const DEFAULT_STATE_FILE: &str = "restaurant.save";
+
+// The most diners a bill can be split between.
+const MAX_DINERS: usize = 100;
+
+const USAGE: &str = "\
+Usage: package_crates_modules [--state <PATH>] <COMMAND>
+
+Commands:
+    open <SEATS>...                   Open for service with one table per seat count
+    menu                              Show the menu
+    waitlist add <NAME> <SIZE>        Put a party on the waitlist
+    waitlist remove <TICKET>          Take a party off the waitlist
+    waitlist                          Show the waitlist
+    book <NAME> <SIZE> <YYYY-MM-DD> <HH:MM> [--table <TABLE>]
+                                      Reserve a table for a time slot (times are UTC)
+    reservations                      Show the reservations
+    arrive <RESERVATION>              Check in a party that has a reservation
+    cancel <RESERVATION>              Cancel a reservation
+    seat                              Seat the next party at the best free table
+    order <TABLE> <DISH[:QTY]>...     Take an order for a seated table, e.g. order 2 soup burger:2
+    quote <DISH[:QTY]>...             Quote how long the kitchen would take over an order
+    send <ORDER>                      Send an order to the kitchen
+    serve <ORDER>                     Serve an order from the kitchen
+    pay <ORDER> [--tax <BASIS POINTS>] [--tip <PERCENT>] [--split <DINERS>]
+                                      Take payment and print the receipt
+    leave <TABLE>                     Clear a table once its orders are paid
+    status                            Show tables, waitlist and open orders
+    report [--csv] [--date <YYYY-MM-DD>] [--utc-offset <MINUTES>]
+                                      Show the end of day report for a day, today by default,
+                                      with hours in local time MINUTES east of UTC
+
+The state of service is kept in restaurant.save unless --state is given.";
+
+// Usage errors exit with 2, everything that went wrong in the restaurant with 1.
+#[derive(Debug)]
+enum CliError {
+    Usage(String),
+    Failed(String),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::Usage(message) | CliError::Failed(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+macro_rules! failed_from {
+    ($($error:ty),*) => {
+        $(impl From<$error> for CliError {
+            fn from(err: $error) -> Self {
+                CliError::Failed(err.to_string())
+            }
+        })*
+    };
+}
+
+failed_from!(
+    HostingError,
+    ReservationError,
+    ServingError,
+    KitchenError,
+    MenuError,
+    BillingError,
+    PersistError
+);
+
+fn usage(message: &str) -> CliError {
+    CliError::Usage(message.to_string())
+}
+
+fn parse<T: std::str::FromStr>(value: &str, what: &str) -> Result<T, CliError> {
+    value
+        .parse()
+        .map_err(|_| usage(&format!("invalid {what} '{value}'")))
+}
+
+// Ids are accepted as printed too: ticket #3, table T2, order O5, reservation R4.
+fn parse_id(value: &str, prefix: char, what: &str) -> Result<u32, CliError> {
+    let number = match value.chars().next() {
+        Some(first) if first.eq_ignore_ascii_case(&prefix) => &value[first.len_utf8()..],
+        _ => value,
+    };
+    parse(number, what).map_err(|_| usage(&format!("invalid {what} '{value}'")))
+}
+
+fn parse_table(value: &str) -> Result<TableId, CliError> {
+    parse_id(value, 'T', "table").map(|id| TableId(id as usize))
+}
+
+// A slot given as YYYY-MM-DD and HH:MM.
+fn parse_slot(date: &str, time: &str) -> Result<Timestamp, CliError> {
+    let invalid = || usage(&format!("invalid date and time '{date} {time}'"));
+    let date: Vec<&str> = date.split('-').collect();
+    let time: Vec<&str> = time.split(':').collect();
+    let (&[year, month, day], &[hour, minute]) = (date.as_slice(), time.as_slice()) else {
+        return Err(invalid());
+    };
+    let number = |field: &str| field.parse::<u32>().map_err(|_| invalid());
+    Timestamp::from_date_time(
+        number(year)? as i64,
+        number(month)?,
+        number(day)?,
+        number(hour)?,
+        number(minute)?,
+    )
+    .ok_or_else(invalid)
+}
+
+fn option_value<'a, T, I>(options: &mut I, what: &str) -> Result<T, CliError>
+where
+    T: std::str::FromStr,
+    I: Iterator<Item = &'a String>,
+{
+    let value = options
+        .next()
+        .ok_or_else(|| usage(&format!("missing {what}")))?;
+    parse(value, what)
+}
 
 fn main() {
-    println!("Hello, world!");
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let mut state = PathBuf::from(DEFAULT_STATE_FILE);
+    if args.first().map(String::as_str) == Some("--state") {
+        if args.len() < 2 {
+            eprintln!("error: missing path for --state");
+            process::exit(2);
+        }
+        state = PathBuf::from(args.remove(1));
+        args.remove(0);
+    }
+
+    if let Err(err) = run(&state, &args) {
+        eprintln!("error: {err}");
+        match err {
+            CliError::Usage(_) => {
+                eprintln!("{USAGE}");
+                process::exit(2);
+            }
+            CliError::Failed(_) => process::exit(1),
+        }
+    }
+}
+
+fn run(state: &Path, args: &[String]) -> Result<(), CliError> {
+    let command = args.first().map(String::as_str);
+    match command {
+        None | Some("help") | Some("--help") | Some("-h") => {
+            println!("{USAGE}");
+            return Ok(());
+        }
+        Some("open") => return open(state, &args[1..]),
+        Some("menu") => {
+            print_menu(&Menu::house());
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let mut restaurant = Restaurant::load(state).map_err(|err| match err {
+        PersistError::Io(_) => CliError::Failed(format!("{err} (run 'open' to start service)")),
+        err => err.into(),
+    })?;
+    let changed = match (command.unwrap_or_default(), &args[1..]) {
+        ("waitlist", [sub, name, size]) if sub == "add" => {
+            let size = parse(size, "party size")?;
+            let ticket = restaurant.add_to_waitlist(name, size, Timestamp::now())?;
+            println!("{name} (party of {size}) is on the waitlist with ticket {ticket}.");
+            true
+        }
+        ("waitlist", [sub, ticket]) if sub == "remove" => {
+            let party = restaurant.leave_waitlist(Ticket(parse_id(ticket, '#', "ticket")?))?;
+            println!("{} left the waitlist.", party.name);
+            true
+        }
+        ("waitlist", []) => {
+            print_waitlist(&restaurant);
+            false
+        }
+        ("book", [name, size, date, time, options @ ..]) => {
+            let size = parse(size, "party size")?;
+            let start = parse_slot(date, time)?;
+            let id = match options {
+                [] => restaurant.book(name, size, start, Timestamp::now())?,
+                [option, table] if option == "--table" => {
+                    let table = parse_table(table)?;
+                    restaurant.book_table(table, name, size, start, Timestamp::now())?
+                }
+                _ => return Err(usage("book takes only --table <TABLE> after the time")),
+            };
+            let reservation = restaurant.reservation(id)?;
+            println!(
+                "Reservation {id}: {name} (party of {size}) at table {} on {start}.",
+                reservation.table
+            );
+            true
+        }
+        ("reservations", []) => {
+            print_reservations(&restaurant);
+            false
+        }
+        ("arrive", [reservation]) => {
+            let id = ReservationId(parse_id(reservation, 'R', "reservation")?);
+            let ticket = restaurant.check_in(id, Timestamp::now())?;
+            println!("Reservation {id} has arrived and has ticket {ticket}.");
+            true
+        }
+        ("cancel", [reservation]) => {
+            let id = ReservationId(parse_id(reservation, 'R', "reservation")?);
+            restaurant.cancel_reservation(id)?;
+            println!("Reservation {id} cancelled.");
+            true
+        }
+        ("seat", []) => {
+            let now = Timestamp::now();
+            for reservation in restaurant.release_no_shows(now) {
+                println!(
+                    "Released reservation {} for {}: no-show.",
+                    reservation.id, reservation.name
+                );
+            }
+            // Released no-shows are saved even if nobody can be seated.
+            let seated = restaurant.seat_at_table(now);
+            restaurant.save(state)?;
+            let seating = seated?;
+            let reservation = match seating.reservation {
+                Some(id) => format!(", reservation {id}"),
+                None => String::new(),
+            };
+            println!(
+                "Seated {} (party of {}, ticket {}{reservation}) at table {}.",
+                seating.party.name, seating.party.size, seating.party.ticket, seating.table
+            );
+            false
+        }
+        ("order", [table, dishes @ ..]) if !dishes.is_empty() => {
+            let table = parse_table(table)?;
+            let items = line_items(&Menu::house(), dishes)?;
+            let id = restaurant.take_order(table, items)?;
+            let total = restaurant.order(id)?.total_cents();
+            println!(
+                "Order {id} taken for table {table} ({}).",
+                format_cents(total)
+            );
+            true
+        }
+        ("quote", dishes) if !dishes.is_empty() => {
+            let menu = Menu::house();
+            let items = line_items(&menu, dishes)?;
+            let minutes = restaurant.quote_wait(&items, &menu, Timestamp::now())?;
+            println!("The kitchen would have that ready in {minutes} min.");
+            false
+        }
+        ("send", [order]) => {
+            let id = OrderId(parse_id(order, 'O', "order")?);
+            let ticket = restaurant.send_to_kitchen(id, &Menu::house(), Timestamp::now())?;
+            println!(
+                "Order {id} sent to the kitchen, ready in {} min.",
+                ticket.minutes_left(ticket.received_at)
+            );
+            true
+        }
+        ("serve", [order]) => {
+            let id = OrderId(parse_id(order, 'O', "order")?);
+            restaurant.serve(id)?;
+            println!("Order {id} served.");
+            true
+        }
+        ("pay", [order, options @ ..]) => {
+            pay(
+                &mut restaurant,
+                OrderId(parse_id(order, 'O', "order")?),
+                options,
+            )?;
+            true
+        }
+        ("leave", [table]) => {
+            let table = parse_table(table)?;
+            let party = restaurant.free_table(table, Timestamp::now())?;
+            println!("{} left table {table}.", party.name);
+            true
+        }
+        ("status", []) => {
+            print_status(&restaurant);
+            false
+        }
+        ("report", options) => {
+            report(&restaurant, options)?;
+            false
+        }
+        (command, _) => {
+            return Err(usage(&format!(
+                "unknown command or arguments for '{command}'"
+            )));
+        }
+    };
+
+    if changed {
+        restaurant.save(state)?;
+    }
+    Ok(())
+}
+
+fn open(state: &Path, seats: &[String]) -> Result<(), CliError> {
+    if seats.is_empty() {
+        return Err(usage("open needs at least one table size"));
+    }
+    if state.exists() {
+        return Err(CliError::Failed(format!(
+            "service is already open ({} exists)",
+            state.display()
+        )));
+    }
+    let capacities = seats
+        .iter()
+        .map(|seats| parse(seats, "table size"))
+        .collect::<Result<Vec<u32>, CliError>>()?;
+    let restaurant = Restaurant::new(&capacities);
+    restaurant.save(state)?;
+    println!("Open for service with {} tables.", capacities.len());
+    Ok(())
+}
+
+// Order lines written as DISH or DISH:QUANTITY, priced from the menu.
+fn line_items(menu: &Menu, dishes: &[String]) -> Result<Vec<LineItem>, CliError> {
+    let mut items = Vec::new();
+    for dish in dishes {
+        let (name, quantity) = match dish.split_once(':') {
+            Some((name, quantity)) => (name, parse(quantity, "quantity")?),
+            None => (dish.as_str(), 1),
+        };
+        items.push(menu.line_item(name, quantity)?);
+    }
+    Ok(items)
+}
+
+fn pay(restaurant: &mut Restaurant, id: OrderId, options: &[String]) -> Result<(), CliError> {
+    let mut rates = Rates::default();
+    let mut split = None;
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--tax" => rates.tax_basis_points = option_value(&mut options, "tax rate")?,
+            "--tip" => rates.tip_percent = option_value(&mut options, "tip percent")?,
+            "--split" => split = Some(option_value(&mut options, "number of diners")?),
+            other => return Err(usage(&format!("unknown option '{other}' for pay"))),
+        }
+    }
+
+    // Checked before the order is paid, so a bad split never leaves a
+    // printed receipt for an order that was not saved as paid.
+    if let Some(diners) = split
+        && !(1..=MAX_DINERS).contains(&diners)
+    {
+        return Err(usage(&format!(
+            "a bill can be split between 1 and {MAX_DINERS} diners"
+        )));
+    }
+
+    let bill = restaurant.take_payment(id, rates, Timestamp::now())?;
+    let shares = split.map(|diners| bill.split_evenly(diners)).transpose()?;
+    println!("Receipt for order {id}");
+    println!("{bill}");
+    if let Some(shares) = shares {
+        println!();
+        print!("{}", bill.render_split(&shares));
+    }
+    Ok(())
+}
+
+fn report(restaurant: &Restaurant, options: &[String]) -> Result<(), CliError> {
+    let mut csv = false;
+    let mut date = None;
+    let mut utc_offset_minutes: i32 = 0;
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--csv" => csv = true,
+            "--date" => {
+                let value = options.next().ok_or_else(|| usage("missing date"))?;
+                date = Some(parse_slot(value, "00:00")?.date());
+            }
+            "--utc-offset" => {
+                utc_offset_minutes = option_value(&mut options, "UTC offset")?;
+            }
+            other => return Err(usage(&format!("unknown option '{other}' for report"))),
+        }
+    }
+    if utc_offset_minutes.abs() >= 24 * 60 {
+        return Err(usage("the UTC offset must be less than a day"));
+    }
+
+    let (year, month, day) = date.unwrap_or_else(|| {
+        let local = Timestamp::now().0 as i64 + utc_offset_minutes as i64 * 60;
+        Timestamp(local.max(0) as u64).date()
+    });
+    let period = ReportPeriod::day(year, month, day, utc_offset_minutes)
+        .ok_or_else(|| usage("the report day starts before 1970"))?;
+    let report = DayReport::build(restaurant, period);
+    if csv {
+        print!("{}", report.to_csv());
+    } else {
+        print!("{}", report.to_text());
+    }
+    Ok(())
+}
+
+fn print_menu(menu: &Menu) {
+    println!(
+        "{:<10} {:>8} {:>6}  {:<8} Ingredients",
+        "Dish", "Price", "Prep", "Station"
+    );
+    for dish in menu.dishes() {
+        println!(
+            "{:<10} {:>8} {:>4}m  {:<8} {}",
+            dish.name,
+            format_cents(dish.price_cents),
+            dish.prep_minutes,
+            dish.station.to_string(),
+            dish.ingredients.join(", ")
+        );
+    }
+}
+
+fn print_waitlist(restaurant: &Restaurant) {
+    println!("{:<8} {:<20} {:>4}", "Ticket", "Name", "Size");
+    for party in restaurant.waitlist() {
+        println!(
+            "{:<8} {:<20} {:>4}",
+            party.ticket.to_string(),
+            party.name,
+            party.size
+        );
+    }
+}
+
+fn print_reservations(restaurant: &Restaurant) {
+    println!(
+        "{:<6} {:<16} {:<6} {:<20} {:>4}  State",
+        "Id", "Slot", "Table", "Name", "Size"
+    );
+    for reservation in restaurant.reservations() {
+        println!(
+            "{:<6} {:<16} {:<6} {:<20} {:>4}  {}",
+            reservation.id.to_string(),
+            reservation.start.to_string(),
+            reservation.table.to_string(),
+            reservation.name,
+            reservation.size,
+            reservation.state()
+        );
+    }
+}
+
+fn print_status(restaurant: &Restaurant) {
+    println!("Tables");
+    println!(
+        "{:<6} {:>5}  {:<20} {:>4}",
+        "Table", "Seats", "Party", "Size"
+    );
+    for table in restaurant.tables() {
+        let (name, size) = match table.occupant() {
+            Some(party) => (party.name.as_str(), party.size.to_string()),
+            None => ("-", String::new()),
+        };
+        println!(
+            "{:<6} {:>5}  {:<20} {:>4}",
+            table.id.to_string(),
+            table.capacity,
+            name,
+            size
+        );
+    }
+
+    println!();
+    println!("Waitlist");
+    print_waitlist(restaurant);
+
+    println!();
+    println!("Open orders");
+    println!(
+        "{:<6} {:<6} {:<11} {:>10}",
+        "Order", "Table", "State", "Total"
+    );
+    for order in restaurant.open_orders() {
+        println!(
+            "{:<6} {:<6} {:<11} {:>10}",
+            order.id().to_string(),
+            order.table().to_string(),
+            order.state().to_string(),
+            format_cents(order.total_cents())
+        );
+    }
+
+    println!();
+    println!("Kitchen");
+    println!("{:<6} {:<16} {:>8}", "Order", "Ready at", "Min left");
+    let now = Timestamp::now();
+    for ticket in restaurant.kitchen().tickets() {
+        println!(
+            "{:<6} {:<16} {:>8}",
+            ticket.order.to_string(),
+            ticket.ready_at.to_string(),
+            ticket.minutes_left(now)
+        );
+    }
 }
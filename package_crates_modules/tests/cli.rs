@@ -0,0 +1,250 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+// A state file of its own for each test, removed when the test ends.
+struct Service {
+    state: PathBuf,
+}
+
+impl Service {
+    fn new(name: &str) -> Service {
+        let state = std::env::temp_dir().join(format!(
+            "package_crates_modules_{name}_{}.save",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&state);
+        Service { state }
+    }
+
+    // Runs the binary and returns its exit code, stdout and stderr.
+    fn run(&self, args: &[&str]) -> (i32, String, String) {
+        let output = Command::new(env!("CARGO_BIN_EXE_package_crates_modules"))
+            .arg("--state")
+            .arg(&self.state)
+            .args(args)
+            .output()
+            .expect("the binary runs");
+        (
+            output.status.code().expect("the binary exits normally"),
+            String::from_utf8(output.stdout).unwrap(),
+            String::from_utf8(output.stderr).unwrap(),
+        )
+    }
+
+    fn ok(&self, args: &[&str]) -> String {
+        let (code, stdout, stderr) = self.run(args);
+        assert_eq!(code, 0, "{args:?} failed: {stderr}");
+        stdout
+    }
+
+    fn error(&self, args: &[&str]) -> (i32, String) {
+        let (code, _, stderr) = self.run(args);
+        let first = stderr.lines().next().unwrap_or_default().to_string();
+        (code, first)
+    }
+}
+
+impl Drop for Service {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.state);
+    }
+}
+
+#[test]
+fn serves_a_party_from_waitlist_to_payment() {
+    let service = Service::new("full_service");
+    assert_eq!(
+        service.ok(&["open", "2", "4"]),
+        "Open for service with 2 tables.\n"
+    );
+    assert_eq!(
+        service.ok(&["waitlist", "add", "Ada Lovelace", "3"]),
+        "Ada Lovelace (party of 3) is on the waitlist with ticket #1.\n"
+    );
+    assert_eq!(
+        service.ok(&["seat"]),
+        "Seated Ada Lovelace (party of 3, ticket #1) at table T2.\n"
+    );
+    assert_eq!(
+        service.ok(&["order", "T2", "soup", "burger:2"]),
+        "Order O1 taken for table T2 ($35.50).\n"
+    );
+    assert_eq!(
+        service.ok(&["quote", "soup", "burger:2"]),
+        "The kitchen would have that ready in 24 min.\n"
+    );
+    assert_eq!(
+        service.ok(&["send", "O1"]),
+        "Order O1 sent to the kitchen, ready in 24 min.\n"
+    );
+    let status = service.ok(&["status"]);
+    let kitchen = status.split("\nKitchen\n").nth(1).unwrap();
+    assert!(
+        kitchen.lines().nth(1).unwrap().starts_with("O1 "),
+        "{status}"
+    );
+    assert_eq!(
+        service.ok(&["quote", "fries"]),
+        "The kitchen would have that ready in 6 min.\n"
+    );
+    assert_eq!(service.ok(&["serve", "o1"]), "Order O1 served.\n");
+
+    let receipt = service.ok(&["pay", "1", "--tax", "0", "--tip", "10", "--split", "2"]);
+    assert!(receipt.starts_with("Receipt for order O1\n"));
+    assert!(receipt.contains("Total                          $39.05\n"));
+    assert!(receipt.contains("$1.78 =     $19.53\n"));
+    assert!(receipt.contains("$1.77 =     $19.52\n"));
+
+    assert_eq!(
+        service.ok(&["leave", "t2"]),
+        "Ada Lovelace left table T2.\n"
+    );
+    let status = service.ok(&["status"]);
+    assert!(status.contains("T2         4  -"));
+    // The served order is no longer on the kitchen queue.
+    assert!(
+        status.ends_with("Kitchen\nOrder  Ready at         Min left\n"),
+        "{status}"
+    );
+}
+
+#[test]
+fn ids_take_their_own_prefix_only() {
+    let service = Service::new("prefixes");
+    service.ok(&["open", "4", "4"]);
+    service.ok(&["waitlist", "add", "Ada", "2"]);
+    service.ok(&["waitlist", "add", "Bob", "2"]);
+    assert_eq!(
+        service.ok(&["waitlist", "remove", "#2"]),
+        "Bob left the waitlist.\n"
+    );
+    assert_eq!(
+        service.ok(&["book", "Cy", "2", "2099-01-01", "19:00", "--table", "T1"]),
+        "Reservation R1: Cy (party of 2) at table T1 on 2099-01-01 19:00.\n"
+    );
+    assert_eq!(service.ok(&["cancel", "r1"]), "Reservation R1 cancelled.\n");
+
+    // Plain numbers do not take an id prefix.
+    assert_eq!(
+        service.error(&["waitlist", "add", "Dee", "t2"]),
+        (2, "error: invalid party size 't2'".to_string())
+    );
+    assert_eq!(
+        service.error(&["waitlist", "add", "Dee", "#2"]),
+        (2, "error: invalid party size '#2'".to_string())
+    );
+    service.ok(&["seat"]);
+    assert_eq!(
+        service.error(&["order", "T1", "soup:O2"]),
+        (2, "error: invalid quantity 'O2'".to_string())
+    );
+    // An id only takes the prefix of its own kind.
+    assert_eq!(
+        service.error(&["leave", "R1"]),
+        (2, "error: invalid table 'R1'".to_string())
+    );
+    assert_eq!(
+        service.error(&["send", "T1"]),
+        (2, "error: invalid order 'T1'".to_string())
+    );
+    assert_eq!(
+        service.error(&["cancel", "RR1"]),
+        (2, "error: invalid reservation 'RR1'".to_string())
+    );
+}
+
+#[test]
+fn exit_codes_tell_usage_errors_from_refusals() {
+    let service = Service::new("exit_codes");
+    let (code, message) = service.error(&["status"]);
+    assert_eq!(code, 1);
+    assert!(message.contains("run 'open' to start service"), "{message}");
+
+    assert_eq!(
+        service.error(&["open", "T4"]),
+        (2, "error: invalid table size 'T4'".to_string())
+    );
+    service.ok(&["open", "2"]);
+    assert_eq!(
+        service.error(&["open", "2"]).0,
+        1,
+        "opening twice is refused"
+    );
+    assert_eq!(
+        service.error(&["dance"]),
+        (
+            2,
+            "error: unknown command or arguments for 'dance'".to_string()
+        )
+    );
+    assert_eq!(
+        service.error(&["waitlist", "add", " ", "2"]),
+        (1, "error: a party needs a name".to_string())
+    );
+    assert_eq!(
+        service.error(&["leave", "T1"]),
+        (1, "error: table T1 is not occupied".to_string())
+    );
+    assert_eq!(
+        service.error(&["seat"]),
+        (1, "error: nobody is waiting".to_string())
+    );
+}
+
+#[test]
+fn a_bad_split_leaves_the_order_unpaid() {
+    let service = Service::new("split");
+    service.ok(&["open", "4"]);
+    service.ok(&["waitlist", "add", "Ada", "2"]);
+    service.ok(&["seat"]);
+    service.ok(&["order", "T1", "soup"]);
+    service.ok(&["send", "O1"]);
+    service.ok(&["serve", "O1"]);
+
+    for diners in ["0", "101", "4000000000"] {
+        let (code, stdout, stderr) = service.run(&["pay", "O1", "--split", diners]);
+        assert_eq!(code, 2, "{diners}");
+        assert_eq!(stdout, "", "{diners}");
+        assert_eq!(
+            stderr.lines().next(),
+            Some("error: a bill can be split between 1 and 100 diners")
+        );
+    }
+    assert!(service.ok(&["status"]).contains("O1     T1     served"));
+
+    let receipt = service.ok(&["pay", "O1", "--split", "100"]);
+    assert!(receipt.starts_with("Receipt for order O1\n"));
+    assert_eq!(
+        service.error(&["pay", "O1"]),
+        (
+            1,
+            "error: order O1 is paid and cannot become paid".to_string()
+        )
+    );
+}
+
+#[test]
+fn report_covers_the_chosen_day() {
+    let service = Service::new("report");
+    service.ok(&["open", "2"]);
+    let csv = service.ok(&["report", "--csv", "--date", "2099-01-01"]);
+    assert!(csv.starts_with("section,name,count,value\nsummary,parties_seated,0,\n"));
+    let text = service.ok(&["report", "--utc-offset", "-300"]);
+    assert!(text.starts_with("End of day report\n"));
+
+    assert_eq!(
+        service.error(&["report", "--date", "2099-02-30"]),
+        (
+            2,
+            "error: invalid date and time '2099-02-30 00:00'".to_string()
+        )
+    );
+    assert_eq!(
+        service.error(&["report", "--utc-offset", "1440"]),
+        (
+            2,
+            "error: the UTC offset must be less than a day".to_string()
+        )
+    );
+}
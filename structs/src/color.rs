@@ -0,0 +1,378 @@
+// **** Colors ****
+// Color is an RGB tuple struct: Color(red, green, blue), each channel 0-255.
+// Hsv and Hsl have named fields so their parts cannot be mixed up:
+// hue in degrees, 0 up to but not including 360, and the other two
+// between 0.0 and 1.0.
+//
+// Conversions between Hsv and Hsl are exact; a conversion to Color rounds to
+// the nearest channel value, and every Color survives a trip through Hsv or
+// Hsl and back unchanged.
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsv {
+    pub hue: f64,
+    pub saturation: f64,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub hue: f64,
+    pub saturation: f64,
+    pub lightness: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorError {
+    // An RGB channel outside 0-255.
+    ChannelOutOfRange { channel: &'static str, value: i64 },
+    // A saturation, value or lightness outside 0.0-1.0, or a hue that is not a number.
+    ComponentOutOfRange { component: &'static str, value: f64 },
+    InvalidHex(String),
+}
+
+impl fmt::Display for ColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ColorError::ChannelOutOfRange { channel, value } => {
+                write!(f, "{channel} is {value}, it must be between 0 and 255")
+            }
+            ColorError::ComponentOutOfRange { component, value } => {
+                write!(f, "{component} is {value}, it must be between 0 and 1")
+            }
+            ColorError::InvalidHex(text) => {
+                write!(f, "'{text}' is not a hex color like #00a0ff or #0af")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ColorError {}
+
+fn channel(name: &'static str, value: i64) -> Result<u8, ColorError> {
+    u8::try_from(value).map_err(|_| ColorError::ChannelOutOfRange {
+        channel: name,
+        value,
+    })
+}
+
+fn unit(name: &'static str, value: f64) -> Result<f64, ColorError> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(ColorError::ComponentOutOfRange {
+            component: name,
+            value,
+        })
+    }
+}
+
+// Any finite hue is accepted and turned into the 0-360 range: 370 is 10, -30 is 330.
+fn hue(value: f64) -> Result<f64, ColorError> {
+    if !value.is_finite() {
+        return Err(ColorError::ComponentOutOfRange {
+            component: "hue",
+            value,
+        });
+    }
+    let hue = value.rem_euclid(360.0);
+    // rem_euclid can round a tiny negative hue up to exactly 360.
+    Ok(if hue >= 360.0 { 0.0 } else { hue })
+}
+
+fn to_channel(value: f64) -> u8 {
+    (value * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+impl Color {
+    pub const BLACK: Color = Color(0, 0, 0);
+    pub const WHITE: Color = Color(255, 255, 255);
+
+    // Checks channels that come in as wider integers, such as raw i32 triples.
+    pub fn from_channels(red: i64, green: i64, blue: i64) -> Result<Color, ColorError> {
+        Ok(Color(
+            channel("red", red)?,
+            channel("green", green)?,
+            channel("blue", blue)?,
+        ))
+    }
+
+    pub fn red(&self) -> u8 {
+        self.0
+    }
+
+    pub fn green(&self) -> u8 {
+        self.1
+    }
+
+    pub fn blue(&self) -> u8 {
+        self.2
+    }
+
+    // Accepts #rrggbb and the short #rgb, with or without the '#'.
+    pub fn from_hex(text: &str) -> Result<Color, ColorError> {
+        let invalid = || ColorError::InvalidHex(text.to_string());
+        let digits = text.strip_prefix('#').unwrap_or(text);
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let parse = |pair: &str| u8::from_str_radix(pair, 16).map_err(|_| invalid());
+        match digits.len() {
+            6 => Ok(Color(
+                parse(&digits[0..2])?,
+                parse(&digits[2..4])?,
+                parse(&digits[4..6])?,
+            )),
+            // #0af is #00aaff: each digit is doubled.
+            3 => Ok(Color(
+                parse(&digits[0..1])? * 17,
+                parse(&digits[1..2])? * 17,
+                parse(&digits[2..3])? * 17,
+            )),
+            _ => Err(invalid()),
+        }
+    }
+
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+
+    // (red, green, blue) scaled to 0.0-1.0, with the largest and smallest of them.
+    fn unit_channels(&self) -> (f64, f64, f64, f64, f64) {
+        let (r, g, b) = (
+            self.0 as f64 / 255.0,
+            self.1 as f64 / 255.0,
+            self.2 as f64 / 255.0,
+        );
+        (r, g, b, r.max(g).max(b), r.min(g).min(b))
+    }
+
+    // The hue shared by HSV and HSL; grays have hue 0.
+    fn hue(&self) -> f64 {
+        let (r, g, b, max, min) = self.unit_channels();
+        let chroma = max - min;
+        if chroma == 0.0 {
+            return 0.0;
+        }
+        let sector = if max == r {
+            ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / chroma + 2.0
+        } else {
+            (r - g) / chroma + 4.0
+        };
+        sector * 60.0
+    }
+
+    pub fn to_hsv(&self) -> Hsv {
+        let (_, _, _, max, min) = self.unit_channels();
+        Hsv {
+            hue: self.hue(),
+            saturation: if max == 0.0 { 0.0 } else { (max - min) / max },
+            value: max,
+        }
+    }
+
+    pub fn to_hsl(&self) -> Hsl {
+        let (_, _, _, max, min) = self.unit_channels();
+        let lightness = (max + min) / 2.0;
+        let saturation = if max == min {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+        Hsl {
+            hue: self.hue(),
+            saturation,
+            lightness,
+        }
+    }
+
+    // Mixes in `amount` of `other`: 0.0 gives this color, 1.0 gives `other`.
+    // The amount is clamped to 0.0-1.0.
+    pub fn blend(&self, other: &Color, amount: f64) -> Color {
+        let amount = if amount.is_nan() {
+            0.0
+        } else {
+            amount.clamp(0.0, 1.0)
+        };
+        let mix = |from: u8, to: u8| {
+            (from as f64 + (to as f64 - from as f64) * amount)
+                .round()
+                .clamp(0.0, 255.0) as u8
+        };
+        Color(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+
+    // Relative luminance as defined by WCAG 2: 0.0 for black, 1.0 for white.
+    pub fn relative_luminance(&self) -> f64 {
+        let linear = |channel: u8| {
+            let c = channel as f64 / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linear(self.0) + 0.7152 * linear(self.1) + 0.0722 * linear(self.2)
+    }
+
+    // The WCAG contrast ratio, from 1.0 (same luminance) to 21.0 (black on white).
+    pub fn contrast_ratio(&self, other: &Color) -> f64 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    // Whether normal-sized text in this color can be read on `background`
+    // (WCAG level AA asks for a ratio of at least 4.5).
+    pub fn is_readable_on(&self, background: &Color) -> bool {
+        self.contrast_ratio(background) >= 4.5
+    }
+}
+
+impl TryFrom<(i32, i32, i32)> for Color {
+    type Error = ColorError;
+
+    fn try_from((red, green, blue): (i32, i32, i32)) -> Result<Self, Self::Error> {
+        Color::from_channels(red as i64, green as i64, blue as i64)
+    }
+}
+
+impl FromStr for Color {
+    type Err = ColorError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Color::from_hex(text)
+    }
+}
+
+// Prints the hex form, #00a0ff.
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+impl Hsv {
+    pub fn new(hue_degrees: f64, saturation: f64, value: f64) -> Result<Hsv, ColorError> {
+        Ok(Hsv {
+            hue: hue(hue_degrees)?,
+            saturation: unit("saturation", saturation)?,
+            value: unit("value", value)?,
+        })
+    }
+
+    pub fn to_rgb(&self) -> Color {
+        let chroma = self.value * self.saturation;
+        from_hue(self.hue, chroma, self.value - chroma)
+    }
+
+    pub fn to_hsl(&self) -> Hsl {
+        let lightness = self.value * (1.0 - self.saturation / 2.0);
+        let saturation = if lightness == 0.0 || lightness == 1.0 {
+            0.0
+        } else {
+            (self.value - lightness) / lightness.min(1.0 - lightness)
+        };
+        Hsl {
+            hue: self.hue,
+            saturation,
+            lightness,
+        }
+    }
+}
+
+impl Hsl {
+    pub fn new(hue_degrees: f64, saturation: f64, lightness: f64) -> Result<Hsl, ColorError> {
+        Ok(Hsl {
+            hue: hue(hue_degrees)?,
+            saturation: unit("saturation", saturation)?,
+            lightness: unit("lightness", lightness)?,
+        })
+    }
+
+    pub fn to_rgb(&self) -> Color {
+        let chroma = (1.0 - (2.0 * self.lightness - 1.0).abs()) * self.saturation;
+        from_hue(self.hue, chroma, self.lightness - chroma / 2.0)
+    }
+
+    pub fn to_hsv(&self) -> Hsv {
+        let value = self.lightness + self.saturation * self.lightness.min(1.0 - self.lightness);
+        let saturation = if value == 0.0 {
+            0.0
+        } else {
+            2.0 * (1.0 - self.lightness / value)
+        };
+        Hsv {
+            hue: self.hue,
+            saturation,
+            value,
+        }
+    }
+}
+
+// The RGB color with this hue and chroma, lifted by `lightest_missing`
+// (the amount of the weakest channel).
+fn from_hue(hue: f64, chroma: f64, lightest_missing: f64) -> Color {
+    let sector = hue / 60.0;
+    let second = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, second, 0.0),
+        1 => (second, chroma, 0.0),
+        2 => (0.0, chroma, second),
+        3 => (0.0, second, chroma),
+        4 => (second, 0.0, chroma),
+        _ => (chroma, 0.0, second),
+    };
+    Color(
+        to_channel(r + lightest_missing),
+        to_channel(g + lightest_missing),
+        to_channel(b + lightest_missing),
+    )
+}
+
+impl From<Hsv> for Color {
+    fn from(hsv: Hsv) -> Self {
+        hsv.to_rgb()
+    }
+}
+
+impl From<Hsl> for Color {
+    fn from(hsl: Hsl) -> Self {
+        hsl.to_rgb()
+    }
+}
+
+impl From<Color> for Hsv {
+    fn from(color: Color) -> Self {
+        color.to_hsv()
+    }
+}
+
+impl From<Color> for Hsl {
+    fn from(color: Color) -> Self {
+        color.to_hsl()
+    }
+}
+
+impl From<Hsl> for Hsv {
+    fn from(hsl: Hsl) -> Self {
+        hsl.to_hsv()
+    }
+}
+
+impl From<Hsv> for Hsl {
+    fn from(hsv: Hsv) -> Self {
+        hsv.to_hsl()
+    }
+}
@@ -0,0 +1,660 @@
+// **** Import and Export ****
+// The registry can be written out as JSON or CSV, edited by hand or in a
+// spreadsheet, and read back in. Both formats carry the same four fields:
+//
+//   JSON: [{"username": "Elijah", "email": "e@example.com", "sign_in_count": 1, "active": true}, ...]
+//   CSV:  username,email,sign_in_count,active
+//         Elijah,e@example.com,1,true
+//
+// On import a file that cannot be read as JSON or CSV at all is an error.
+// A row with a bad value (an invalid email, a username that is already taken)
+// is skipped and reported, and every other row is still imported.
+// sign_in_count and active may be left out of a row; they default to 0 and true.
+// Sessions, locks and audit logs are not exported: an imported user starts
+// signed out, unlocked and with an empty log.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::registry::{RegistryError, UserRegistry};
+use crate::user::User;
+
+const CSV_HEADER: [&str; 4] = ["username", "email", "sign_in_count", "active"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+impl Format {
+    // Picks the format from a .json or .csv extension.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+// Why the whole file was rejected.
+#[derive(Debug)]
+pub enum ImportError {
+    Io(io::Error),
+    UnknownFormat(String),
+    Json { line: usize, message: String },
+    Csv { line: usize, message: String },
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImportError::Io(err) => write!(f, "could not access the file: {err}"),
+            ImportError::UnknownFormat(path) => {
+                write!(f, "{path} does not end in .json or .csv")
+            }
+            ImportError::Json { line, message } => write!(f, "JSON line {line}: {message}"),
+            ImportError::Csv { line, message } => write!(f, "CSV line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl From<io::Error> for ImportError {
+    fn from(err: io::Error) -> Self {
+        ImportError::Io(err)
+    }
+}
+
+// Why one row was skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RowProblem {
+    MissingField(&'static str),
+    InvalidField { field: &'static str, value: String },
+    NotAnObject,
+    Rejected(RegistryError),
+}
+
+impl fmt::Display for RowProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RowProblem::MissingField(field) => write!(f, "{field} is missing"),
+            RowProblem::InvalidField { field, value } => {
+                write!(f, "'{value}' is not a valid {field}")
+            }
+            RowProblem::NotAnObject => write!(f, "a user must be a JSON object"),
+            RowProblem::Rejected(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+// `row` is the position of the user in a JSON array, counting from 1,
+// or the line of a CSV file, where the header is line 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowError {
+    pub row: usize,
+    pub problem: RowProblem,
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "row {}: {}", self.row, self.problem)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub imported: usize,
+    pub errors: Vec<RowError>,
+}
+
+// The four fields of one row before they are checked.
+#[derive(Default)]
+struct RawUser {
+    username: Option<String>,
+    email: Option<String>,
+    sign_in_count: Option<String>,
+    active: Option<String>,
+}
+
+impl RawUser {
+    fn into_user(self) -> Result<User, RowProblem> {
+        let username = self.username.ok_or(RowProblem::MissingField("username"))?;
+        let email = self.email.ok_or(RowProblem::MissingField("email"))?;
+        let sign_in_count = match self.sign_in_count {
+            Some(value) => value.trim().parse().map_err(|_| RowProblem::InvalidField {
+                field: "sign_in_count",
+                value,
+            })?,
+            None => 0,
+        };
+        let active = match self.active.as_deref().map(str::trim) {
+            None | Some("true") => true,
+            Some("false") => false,
+            Some(value) => {
+                return Err(RowProblem::InvalidField {
+                    field: "active",
+                    value: value.to_string(),
+                });
+            }
+        };
+        User::builder()
+            .username(username)
+            .email(email)
+            .sign_in_count(sign_in_count)
+            .active(active)
+            .build()
+            .map_err(|err| RowProblem::Rejected(err.into()))
+    }
+}
+
+impl UserRegistry {
+    pub fn export(&self, format: Format) -> String {
+        match format {
+            Format::Json => self.to_json(),
+            Format::Csv => self.to_csv(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let rows: Vec<String> = self
+            .users()
+            .map(|user| {
+                format!(
+                    "  {{\"username\": {}, \"email\": {}, \"sign_in_count\": {}, \"active\": {}}}",
+                    json_string(user.username()),
+                    json_string(user.email()),
+                    user.sign_in_count(),
+                    user.is_active()
+                )
+            })
+            .collect();
+        if rows.is_empty() {
+            String::from("[]\n")
+        } else {
+            format!("[\n{}\n]\n", rows.join(",\n"))
+        }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = CSV_HEADER.join(",");
+        csv.push('\n');
+        for user in self.users() {
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                csv_field(user.username()),
+                csv_field(user.email()),
+                user.sign_in_count(),
+                user.is_active()
+            ));
+        }
+        csv
+    }
+
+    pub fn import(&mut self, text: &str, format: Format) -> Result<ImportReport, ImportError> {
+        match format {
+            Format::Json => self.import_json(text),
+            Format::Csv => self.import_csv(text),
+        }
+    }
+
+    pub fn import_json(&mut self, text: &str) -> Result<ImportReport, ImportError> {
+        let value = JsonParser::new(text).parse_document()?;
+        let Json::Array(items) = value else {
+            return Err(ImportError::Json {
+                line: 1,
+                message: String::from("expected an array of users"),
+            });
+        };
+        let rows = items.into_iter().enumerate().map(|(index, item)| {
+            let raw = match item {
+                Json::Object(fields) => Ok(raw_from_json(fields)),
+                _ => Err(RowProblem::NotAnObject),
+            };
+            (index + 1, raw)
+        });
+        Ok(self.add_rows(rows))
+    }
+
+    pub fn import_csv(&mut self, text: &str) -> Result<ImportReport, ImportError> {
+        let records = parse_csv(text)?;
+        let Some((_, header)) = records.first() else {
+            return Err(ImportError::Csv {
+                line: 1,
+                message: String::from("the file is empty"),
+            });
+        };
+        let mut columns = [None; 4];
+        for (position, name) in header.iter().enumerate() {
+            match CSV_HEADER.iter().position(|&known| known == name.trim()) {
+                Some(column) => columns[column] = Some(position),
+                None => {
+                    return Err(ImportError::Csv {
+                        line: 1,
+                        message: format!("unknown column '{name}'"),
+                    });
+                }
+            }
+        }
+        for (column, name) in CSV_HEADER.iter().enumerate().take(2) {
+            if columns[column].is_none() {
+                return Err(ImportError::Csv {
+                    line: 1,
+                    message: format!("the header has no {name} column"),
+                });
+            }
+        }
+
+        let rows = records.iter().skip(1).map(|(line, record)| {
+            let field = |column: usize| {
+                columns[column]
+                    .and_then(|position| record.get(position))
+                    .filter(|value| !value.is_empty())
+                    .cloned()
+            };
+            let raw = RawUser {
+                username: field(0),
+                email: field(1),
+                sign_in_count: field(2),
+                active: field(3),
+            };
+            (*line, Ok(raw))
+        });
+        Ok(self.add_rows(rows))
+    }
+
+    // Writes the registry to a .json or .csv file.
+    pub fn save(&self, path: &Path) -> Result<(), ImportError> {
+        let format = format_of(path)?;
+        fs::write(path, self.export(format))?;
+        Ok(())
+    }
+
+    // Imports the users in a .json or .csv file.
+    pub fn import_file(&mut self, path: &Path) -> Result<ImportReport, ImportError> {
+        let format = format_of(path)?;
+        let text = fs::read_to_string(path)?;
+        self.import(&text, format)
+    }
+
+    fn add_rows(
+        &mut self,
+        rows: impl Iterator<Item = (usize, Result<RawUser, RowProblem>)>,
+    ) -> ImportReport {
+        let mut report = ImportReport::default();
+        for (row, raw) in rows {
+            let added = raw
+                .and_then(RawUser::into_user)
+                .and_then(|user| self.add(user).map_err(RowProblem::Rejected));
+            match added {
+                Ok(()) => report.imported += 1,
+                Err(problem) => report.errors.push(RowError { row, problem }),
+            }
+        }
+        report
+    }
+}
+
+fn format_of(path: &Path) -> Result<Format, ImportError> {
+    Format::from_path(path).ok_or_else(|| ImportError::UnknownFormat(path.display().to_string()))
+}
+
+fn raw_from_json(fields: Vec<(String, Json)>) -> RawUser {
+    let mut raw = RawUser::default();
+    for (name, value) in fields {
+        // Numbers and booleans keep their JSON spelling and are checked with the rest.
+        let text = match value {
+            Json::String(text) | Json::Number(text) => Some(text),
+            Json::Bool(flag) => Some(flag.to_string()),
+            Json::Null => None,
+            Json::Array(_) | Json::Object(_) => Some(String::from("(nested value)")),
+        };
+        match name.as_str() {
+            "username" => raw.username = text,
+            "email" => raw.email = text,
+            "sign_in_count" => raw.sign_in_count = text,
+            "active" => raw.active = text,
+            _ => {}
+        }
+    }
+    raw
+}
+
+// **** CSV ****
+// Fields holding a comma, a quote or a line break are quoted, with quotes doubled.
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// Splits the text into records, each with the line it starts on.
+// Blank lines are skipped.
+fn parse_csv(text: &str) -> Result<Vec<(usize, Vec<String>)>, ImportError> {
+    let mut records = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+    while chars.peek().is_some() {
+        let start = line;
+        let mut record = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        loop {
+            match chars.next() {
+                None if quoted => {
+                    return Err(ImportError::Csv {
+                        line: start,
+                        message: String::from("a quoted field is never closed"),
+                    });
+                }
+                None => break,
+                Some('"') if quoted => {
+                    if chars.peek() == Some(&'"') {
+                        chars.next();
+                        field.push('"');
+                    } else {
+                        quoted = false;
+                    }
+                }
+                Some('"') if field.is_empty() => quoted = true,
+                Some('\n') if quoted => {
+                    line += 1;
+                    field.push('\n');
+                }
+                Some(c) if quoted => field.push(c),
+                Some(',') => record.push(std::mem::take(&mut field)),
+                Some('\r') if chars.peek() == Some(&'\n') => {}
+                Some('\n') => {
+                    line += 1;
+                    break;
+                }
+                Some(c) => field.push(c),
+            }
+        }
+        record.push(field);
+        if !(record.len() == 1 && record[0].trim().is_empty()) {
+            records.push((start, record));
+        }
+    }
+    Ok(records)
+}
+
+// **** JSON ****
+// Just enough JSON to read a list of users back: every value kind is
+// understood, and numbers are kept as written.
+
+enum Json {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+fn json_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// Arrays and objects nested deeper than this are refused, so a hostile file
+// cannot run the recursive parser out of stack.
+const MAX_JSON_DEPTH: usize = 64;
+
+struct JsonParser<'a> {
+    text: &'a str,
+    position: usize,
+    // Arrays and objects currently open around the position.
+    depth: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn new(text: &'a str) -> JsonParser<'a> {
+        JsonParser {
+            text,
+            position: 0,
+            depth: 0,
+        }
+    }
+
+    fn parse_document(mut self) -> Result<Json, ImportError> {
+        let value = self.value()?;
+        self.skip_whitespace();
+        if self.position < self.text.len() {
+            return Err(self.error("unexpected text after the end of the document"));
+        }
+        Ok(value)
+    }
+
+    fn error(&self, message: &str) -> ImportError {
+        ImportError::Json {
+            line: self.text[..self.position].matches('\n').count() + 1,
+            message: message.to_string(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ImportError> {
+        self.skip_whitespace();
+        if self.next() == Some(expected) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{expected}'")))
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, ImportError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => Ok(Json::String(self.string()?)),
+            Some('t') => self.literal("true", Json::Bool(true)),
+            Some('f') => self.literal("false", Json::Bool(false)),
+            Some('n') => self.literal("null", Json::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            Some(_) => Err(self.error("expected a value")),
+            None => Err(self.error("the document ends too early")),
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Json) -> Result<Json, ImportError> {
+        if self.text[self.position..].starts_with(word) {
+            self.position += word.len();
+            Ok(value)
+        } else {
+            Err(self.error("expected a value"))
+        }
+    }
+
+    // -?(0|[1-9][0-9]*)(.[0-9]+)?([eE][+-]?[0-9]+)?, as the JSON grammar has it.
+    fn number(&mut self) -> Result<Json, ImportError> {
+        let start = self.position;
+        if self.peek() == Some('-') {
+            self.position += 1;
+        }
+        match self.peek() {
+            Some('0') => {
+                self.position += 1;
+                if matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+                    return Err(self.error("a number cannot start with a zero"));
+                }
+            }
+            Some(c) if c.is_ascii_digit() => self.digits()?,
+            _ => return Err(self.error("expected a digit")),
+        }
+        if self.peek() == Some('.') {
+            self.position += 1;
+            self.digits()?;
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            self.position += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.position += 1;
+            }
+            self.digits()?;
+        }
+        Ok(Json::Number(self.text[start..self.position].to_string()))
+    }
+
+    // One or more digits.
+    fn digits(&mut self) -> Result<(), ImportError> {
+        let start = self.position;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        if self.position == start {
+            return Err(self.error("expected a digit"));
+        }
+        Ok(())
+    }
+
+    fn string(&mut self) -> Result<String, ImportError> {
+        self.expect('"')?;
+        let mut text = String::new();
+        loop {
+            match self.next() {
+                None => return Err(self.error("a string is never closed")),
+                Some('"') => return Ok(text),
+                Some('\\') => match self.next() {
+                    Some('"') => text.push('"'),
+                    Some('\\') => text.push('\\'),
+                    Some('/') => text.push('/'),
+                    Some('b') => text.push('\u{8}'),
+                    Some('f') => text.push('\u{c}'),
+                    Some('n') => text.push('\n'),
+                    Some('r') => text.push('\r'),
+                    Some('t') => text.push('\t'),
+                    Some('u') => text.push(self.unicode_escape()?),
+                    _ => return Err(self.error("unknown escape in a string")),
+                },
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    // The four hex digits after \u, joining a surrogate pair into one character.
+    fn unicode_escape(&mut self) -> Result<char, ImportError> {
+        let high = self.hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if !self.text[self.position..].starts_with("\\u") {
+                return Err(self.error("a surrogate pair is cut in half"));
+            }
+            self.position += 2;
+            let low = self.hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("a surrogate pair is cut in half"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid \\u escape"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, ImportError> {
+        let digits = self
+            .text
+            .get(self.position..self.position + 4)
+            .ok_or_else(|| self.error("invalid \\u escape"))?;
+        let code = u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid \\u escape"))?;
+        self.position += 4;
+        Ok(code)
+    }
+
+    // Parses an array or object with `body`, refusing to go past MAX_JSON_DEPTH.
+    fn nested(
+        &mut self,
+        body: fn(&mut Self) -> Result<Json, ImportError>,
+    ) -> Result<Json, ImportError> {
+        if self.depth == MAX_JSON_DEPTH {
+            return Err(self.error(&format!(
+                "arrays and objects are nested more than {MAX_JSON_DEPTH} deep"
+            )));
+        }
+        self.depth += 1;
+        let value = body(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn array(&mut self) -> Result<Json, ImportError> {
+        self.nested(|parser| {
+            parser.expect('[')?;
+            let mut items = Vec::new();
+            parser.skip_whitespace();
+            if parser.peek() == Some(']') {
+                parser.position += 1;
+                return Ok(Json::Array(items));
+            }
+            loop {
+                items.push(parser.value()?);
+                parser.skip_whitespace();
+                match parser.next() {
+                    Some(',') => continue,
+                    Some(']') => return Ok(Json::Array(items)),
+                    _ => return Err(parser.error("expected ',' or ']'")),
+                }
+            }
+        })
+    }
+
+    fn object(&mut self) -> Result<Json, ImportError> {
+        self.nested(|parser| {
+            parser.expect('{')?;
+            let mut fields = Vec::new();
+            parser.skip_whitespace();
+            if parser.peek() == Some('}') {
+                parser.position += 1;
+                return Ok(Json::Object(fields));
+            }
+            loop {
+                parser.skip_whitespace();
+                let name = parser.string()?;
+                parser.expect(':')?;
+                fields.push((name, parser.value()?));
+                parser.skip_whitespace();
+                match parser.next() {
+                    Some(',') => continue,
+                    Some('}') => return Ok(Json::Object(fields)),
+                    _ => return Err(parser.error("expected ',' or '}'")),
+                }
+            }
+        })
+    }
+}
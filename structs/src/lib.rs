@@ -0,0 +1,27 @@
+// The User model lives in the library crate so it is validated in one
+// place and can be used from the binary and from integration tests.
+pub mod user;
+
+// Storage for users, keeping usernames and emails unique.
+pub mod registry;
+
+// Exporting users to JSON or CSV and importing them back.
+pub mod import_export;
+
+// Positioned rectangles and their geometry.
+pub mod rectangle;
+
+// Packing rectangles into a container.
+pub mod packing;
+
+// Drawing rectangles as SVG or ASCII art.
+pub mod render;
+
+// RGB, HSV and HSL colors and conversions between them.
+pub mod color;
+
+// Points and vectors in 3D.
+pub mod point;
+
+// Sign-in sessions and the audit log.
+pub mod session;
@@ -6,20 +6,27 @@
 // To define a struct, we enter the keyword struct followed by the  name of the struct.
 // we name the fields of the struct inside the curly brackets.
 // The fields are defined in key: value pairs. where name is the key and value is the type.
-struct User {
-    username: String,
-    email: String,
-    sign_in_count: u64,
-    active: bool,
-}
+// User is defined in src/user.rs:
+// struct User {
+//     username: String,
+//     email: String,
+//     sign_in_count: u64,
+//     active: bool,
+// }
+// Its fields are private, so a User is made with User::builder(),
+// which checks the username and email.
+use structs::user::{User, UserError};
  // **** Tuple Structs without Named Fields ****
 // Tuple structs have the added meaning the struct name provides 
 // but don’t have names associated with their fields;
 // Very iseful when you want to give the whole tuple a name
 // and make the tuple a different type from other tuples.
 
-struct Color(i32, i32, i32);
-struct Point(i32, i32, i32);
+// Color is defined the same way in src/color.rs: struct Color(u8, u8, u8).
+// Its channels are u8, so a channel can only hold 0 to 255.
+use structs::color::Color;
+// Point is in src/point.rs as struct Point(i32, i32, i32), with vector math on it.
+use structs::point::Point;
 
 // **** Unit-Like Structs Without Any Fields ****
 // They behave like (), the unit type.
@@ -33,26 +40,20 @@ struct AlwaysEqual;
 
 
 // **** Calculate the Area of a rectangle ****
-#[derive(Debug)] // Specifies how to format the struct when printing it.
-struct Rectangle {
-    width: u32,
-    height: u32,
-}
+// Rectangle lives in the library crate, in src/rectangle.rs, with a position
+// and the rest of its geometry. #[derive(Debug)] on it specifies how to
+// format the struct when printing it.
+use structs::rectangle::{Position, Rectangle};
+use structs::render::{self, Shape};
 
 // **** Methods ****
 // They are similar to functions
 // The difference is that they are defined within the context of a struct.
 // Their first parameters are always self, which represents
 // the instance of the struct the method is being called on.
-impl Rectangle {
-    fn area(&self) -> u32 {
-        self.width * self.height
-    }
-    // Methods can take multiple parameters.
-    fn can_hold(&self, other: &Rectangle) -> bool {
-        self.width > other.width && self.height > other.height
-    }
-}
+// eg fn area(&self) -> Result<u32, GeometryError>
+// Methods can take multiple parameters.
+// eg fn can_hold(&self, other: &Rectangle) -> bool
 // Anything in the implementation block will be associated with 
 // Rectangle type.
 // Methods can take ownership of self,
@@ -70,46 +71,48 @@ impl Rectangle {
 // Associated functions don’t have self as their first parameter,
 // are often used for constructors that will return a new instance 
 // of the struct.
-impl Rectangle {
-    fn square(size: u32) -> self{
-        self {
-            width: size,
-            height: size,
-        }
-    }
-}
+// Self is an alias for the type that appears after the impl keyword.
+// eg fn square(size: u32) -> Self { Self::new(size, size) }
 // to calll the associated function we use the :: syntax with the struct name.
 // eg let sq = Rectangle::square(3);
 
 
 fn main() {
     
-    // We can create an instance of the struct by using the name of the struct
-    let mut user1 = User {
-        email: String::from("elijahgathang@gmail.com"),
-        username: String::from("Elijah"),
-        active: true,
-        sign_in_count: 1,
-    };
+    // We can create an instance of the struct by using the name of the struct,
+    // here through its builder.
+    let mut user1 = User::builder()
+        .email("elijahgathang@gmail.com")
+        .username("Elijah")
+        .active(true)
+        .sign_in_count(1)
+        .build()
+        .unwrap();
     // To get a specific value in a struct, we use dot notation.
-    user1.email = String::from("elijahgathang@gmail.com"); // This is an error.
+    // User's fields are private, so they are read and changed through methods.
+    user1.change_email("elijah@example.com").unwrap();
+    println!("{} has signed in {} times", user1.username(), user1.sign_in_count());
     // The entire instance must be mutable; Rust doesn’t allow us to mark only certain fields as mutable.
     
     // You can create an instance from another instance by using struct update syntax.
-    let user2 = User {
-        active: user1.active,
-        email: String::from("another@example.com"),
-        username: String::from("anotherusername567"),
-        sign_in_count: user1.sign_in_count,
-    };
+    // eg User { email: String::from("another@example.com"), ..user1 }
     // the syntax .. specifies that the remaining fields not explicitly 
     //set should have the same value as the fields in the given instance.
-    let user3 = User {
-        email: String::from("another@example.com"),
-        ..user1
-    };
-    // user3 is a new instance of the User struct.
-    // it has a different email but has the same values for the other fields as user1.
+    // User's fields are private, so here the builder copies them from user1 instead.
+    let user2 = User::builder()
+        .email("another@example.com")
+        .username("anotherusername567")
+        .active(user1.is_active())
+        .sign_in_count(user1.sign_in_count())
+        .build()
+        .unwrap();
+    // user2 is a new instance of the User struct.
+    // it has a different email and username but is as active and signed in as often as user1.
+    println!("{} and {}", user1, user2);
+    match build_user(String::from("third@example.com"), String::from("user3")) {
+        Ok(user3) => println!("built {}", user3),
+        Err(err) => println!("user3: {}", err),
+    }
 
     // **** Using Tuple Structs without Named Fields to Create Different Types ****
     let black = Color(0, 0, 0);
@@ -118,42 +121,60 @@ fn main() {
     // when destructuring struct tuples, you are required to name
     // the type of the struct 
     let Point(x, y, z) = origin;
+    println!("origin is at x = {}, y = {}, z = {}", x, y, z);
+    // Points can be added and subtracted like numbers.
+    let corner = origin + Point(3, 4, 12);
+    println!("{} is {} away from {}", corner, corner.distance(&origin), origin);
+    // A Color prints as a hex string, and can be read back from one.
+    println!("black is {}", black);
+    let sky: Color = "#00a0ff".parse().unwrap();
+    println!("{} on {} has a contrast ratio of {:.1}", sky, black, sky.contrast_ratio(&black));
 
-    let subject = AlwaysEqual; // This is an instance of a struct without any fields.
+    let _subject = AlwaysEqual; // This is an instance of a struct without any fields.
 
 
     // **** Calculating the Area of a Rectangle with a Function ****
-    // let rect1 = Rectangle {
-    //     width: 30,
-    //     height: 50,
-    // };
+    let rect1 = Rectangle::new(30, 50);
+    // The area is checked: a rectangle too big for u32 reports an overflow.
+    match rect1.area() { // This is a method call.
+        Ok(area) => println!("The area of the rectangle is {} square pixels.", area),
+        Err(err) => println!("rect1: {}", err),
+    }
     println!(
-        "The area of the rectangle is {} square pixels.", 
-        rect1.area() // This is a method call.
+        "The area function gives the same {} square pixels.",
+        area(&rect1) // This is a function call.
     );
 
-    let rect2 = Rectangle {
-        width: 10,
-        height: 40,
-    };
-    let rect3 = Rectangle {
-        width: 60,
-        height: 45,
-    };
+    let rect2 = Rectangle::new(10, 40);
+    let rect3 = Rectangle::new(60, 45);
+    let sq = Rectangle::square(3);
 
     // Rust uses automatic derefrencing to make method calls more convenient.
     println!("Can rect1 hold rect2? {}", rect1.can_hold(&rect2));
     println!("Can rect1 hold rect3? {}", rect1.can_hold(&rect3));
+    // can_hold also tries the other rectangle turned by 90 degrees.
+    println!("Can rect1 hold rect2 turned? {}", rect1.can_hold(&rect2.rotated()));
+    println!("Can rect1 hold sq? {}", rect1.can_hold(&sq));
+
+    // Drawing them makes the answers easy to check: rect2 sits inside rect1,
+    // rect3 is drawn next to it and is clearly bigger.
+    let shapes = [
+        Shape::new(rect1, "rect1"),
+        Shape::new(rect2.moved_to(Position::new(10, 8)), "rect2"),
+        Shape::new(rect3.moved_to(Position::new(35, 0)), "rect3"),
+    ];
+    print!("{}", render::to_ascii(&shapes, render::terminal_width()));
     
     // **** Meaningful display with derived traits ****
     println!("rect1 is {:#?}", rect1);
 }
 
-fn area(rectangle: &Rectangle) -> u32 {
-    rectangle.width * rectangle.height
+fn area(rectangle: &Rectangle) -> u64 {
+    // Widening to u64 first means the product cannot overflow.
+    rectangle.width as u64 * rectangle.height as u64
 }
 
-fn build_user(email: String, username: String) -> User{
+fn build_user(email: String, username: String) -> Result<User, UserError> {
     // having to declare the struct fields by name is annyoing.
     // User {
     //     email: email,
@@ -163,10 +184,11 @@ fn build_user(email: String, username: String) -> User{
     // }
 
     // We can use the field init shorthand syntax.
-    User {
-        email,
-        username,
-        active: true,
-        sign_in_count: 1,
-    }
+    // User { email, username, active: true, sign_in_count: 1 }
+    // User's fields are private, so the builder fills them in and checks them.
+    User::builder()
+        .email(email)
+        .username(username)
+        .sign_in_count(1)
+        .build()
 }
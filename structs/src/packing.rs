@@ -0,0 +1,142 @@
+// **** Packing Rectangles ****
+// Places items in a container with the shelf algorithm: the container is
+// filled with horizontal shelves from the top down, and each shelf is filled
+// from left to right. An item is laid on its long side when that fits, which
+// keeps shelves low, and items go in tallest first; each item goes on the shelf
+// whose height it wastes least, and a new shelf is opened below the last one
+// when no shelf has room.
+//
+// Only the sizes of the items count; where they are placed is the result.
+// Items that do not fit anywhere are listed by their index.
+
+use crate::rectangle::{Position, Rectangle};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    // The index of the item in the list that was packed.
+    pub item: usize,
+    // Where the item ended up, in the container's coordinates.
+    pub rect: Rectangle,
+    // Whether the item was turned by 90 degrees.
+    pub rotated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packing {
+    pub container: Rectangle,
+    // In the order of the items.
+    pub placed: Vec<Placement>,
+    pub unplaced: Vec<usize>,
+}
+
+impl Packing {
+    pub fn used_area(&self) -> u64 {
+        self.placed
+            .iter()
+            .map(|placement| placement.rect.width as u64 * placement.rect.height as u64)
+            .sum()
+    }
+
+    // The share of the container covered by items, in percent.
+    pub fn utilization(&self) -> f64 {
+        let container = self.container.width as u64 * self.container.height as u64;
+        if container == 0 {
+            return 0.0;
+        }
+        self.used_area() as f64 * 100.0 / container as f64
+    }
+}
+
+struct Shelf {
+    top: u32,
+    height: u32,
+    used_width: u32,
+}
+
+// The size to place the item at: lying on its long side if that fits
+// the container, standing up otherwise.
+fn orient(container: &Rectangle, item: &Rectangle) -> (u32, u32, bool) {
+    let lying = item.width >= item.height;
+    let (long, short) = if lying {
+        (item.width, item.height)
+    } else {
+        (item.height, item.width)
+    };
+    if long <= container.width && short <= container.height {
+        (long, short, !lying)
+    } else {
+        (short, long, lying)
+    }
+}
+
+pub fn pack_shelves(container: &Rectangle, items: &[Rectangle]) -> Packing {
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    order.sort_by_key(|&index| {
+        let item = &items[index];
+        std::cmp::Reverse(item.width.min(item.height))
+    });
+
+    let mut shelves: Vec<Shelf> = Vec::new();
+    let mut placed = Vec::new();
+    let mut unplaced = Vec::new();
+    for index in order {
+        let item = &items[index];
+        if !container.can_fit(item) {
+            unplaced.push(index);
+            continue;
+        }
+        let (width, height, rotated) = orient(container, item);
+
+        let best = shelves
+            .iter()
+            .enumerate()
+            .filter(|(_, shelf)| {
+                height <= shelf.height && width <= container.width - shelf.used_width
+            })
+            .min_by_key(|(_, shelf)| shelf.height - height)
+            .map(|(position, _)| position);
+        let shelf = match best {
+            Some(position) => position,
+            None => {
+                let top = shelves.last().map_or(0, |shelf| shelf.top + shelf.height);
+                if height > container.height - top {
+                    unplaced.push(index);
+                    continue;
+                }
+                shelves.push(Shelf {
+                    top,
+                    height,
+                    used_width: 0,
+                });
+                shelves.len() - 1
+            }
+        };
+
+        let shelf = &mut shelves[shelf];
+        let x = container.origin.x as i64 + shelf.used_width as i64;
+        let y = container.origin.y as i64 + shelf.top as i64;
+        // A container whose far edge is past i32::MAX has room that cannot be addressed.
+        let (Ok(x), Ok(y)) = (i32::try_from(x), i32::try_from(y)) else {
+            unplaced.push(index);
+            continue;
+        };
+        shelf.used_width += width;
+        placed.push(Placement {
+            item: index,
+            rect: Rectangle {
+                origin: Position { x, y },
+                width,
+                height,
+            },
+            rotated,
+        });
+    }
+
+    placed.sort_by_key(|placement| placement.item);
+    unplaced.sort();
+    Packing {
+        container: *container,
+        placed,
+        unplaced,
+    }
+}
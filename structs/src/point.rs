@@ -0,0 +1,331 @@
+// **** Points in 3D ****
+// Point(x, y, z) is a tuple struct with integer coordinates; PointF is the
+// same with f64 coordinates, for results such as a unit vector or a center
+// that fall between whole numbers. A point doubles as the vector from the
+// origin to it, so points can be added, subtracted and scaled.
+//
+// The +, - and * operators behave like they do on i32: they panic on overflow
+// in debug builds. checked_add, checked_sub and checked_scale return
+// GeometryError::Overflow instead. Dot products and squared distances are
+// computed in i128 and u128, where they cannot overflow.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::rectangle::GeometryError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point(pub i32, pub i32, pub i32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PointF(pub f64, pub f64, pub f64);
+
+// The smallest box, with sides parallel to the axes, that holds a set of points.
+// Both corners are inside the box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+fn checked(
+    x: Option<i32>,
+    y: Option<i32>,
+    z: Option<i32>,
+    what: &'static str,
+) -> Result<Point, GeometryError> {
+    match (x, y, z) {
+        (Some(x), Some(y), Some(z)) => Ok(Point(x, y, z)),
+        _ => Err(GeometryError::Overflow(what)),
+    }
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point(0, 0, 0);
+
+    pub fn x(&self) -> i32 {
+        self.0
+    }
+
+    pub fn y(&self) -> i32 {
+        self.1
+    }
+
+    pub fn z(&self) -> i32 {
+        self.2
+    }
+
+    pub fn checked_add(&self, other: &Point) -> Result<Point, GeometryError> {
+        checked(
+            self.0.checked_add(other.0),
+            self.1.checked_add(other.1),
+            self.2.checked_add(other.2),
+            "sum",
+        )
+    }
+
+    pub fn checked_sub(&self, other: &Point) -> Result<Point, GeometryError> {
+        checked(
+            self.0.checked_sub(other.0),
+            self.1.checked_sub(other.1),
+            self.2.checked_sub(other.2),
+            "difference",
+        )
+    }
+
+    pub fn checked_scale(&self, factor: i32) -> Result<Point, GeometryError> {
+        checked(
+            self.0.checked_mul(factor),
+            self.1.checked_mul(factor),
+            self.2.checked_mul(factor),
+            "scaled point",
+        )
+    }
+
+    pub fn dot(&self, other: &Point) -> i128 {
+        self.0 as i128 * other.0 as i128
+            + self.1 as i128 * other.1 as i128
+            + self.2 as i128 * other.2 as i128
+    }
+
+    // The cross product is perpendicular to both points, seen as vectors.
+    // Its coordinates can be far larger than either input's.
+    pub fn cross(&self, other: &Point) -> Result<Point, GeometryError> {
+        let (a, b) = (self.to_i64(), other.to_i64());
+        let coordinate = |value: i64| i32::try_from(value).ok();
+        checked(
+            coordinate(a.1 * b.2 - a.2 * b.1),
+            coordinate(a.2 * b.0 - a.0 * b.2),
+            coordinate(a.0 * b.1 - a.1 * b.0),
+            "cross product",
+        )
+    }
+
+    fn to_i64(self) -> (i64, i64, i64) {
+        (self.0 as i64, self.1 as i64, self.2 as i64)
+    }
+
+    // Exact, so it can be used to compare distances.
+    pub fn distance_squared(&self, other: &Point) -> u128 {
+        let (a, b) = (self.to_i64(), other.to_i64());
+        let square = |d: i64| (d.unsigned_abs() as u128).pow(2);
+        square(a.0 - b.0) + square(a.1 - b.1) + square(a.2 - b.2)
+    }
+
+    pub fn distance(&self, other: &Point) -> f64 {
+        PointF::from(*self).distance(&PointF::from(*other))
+    }
+
+    pub fn to_f64(&self) -> PointF {
+        PointF::from(*self)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.0, self.1, self.2)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point(self.0 + other.0, self.1 + other.1, self.2 + other.2)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point(self.0 - other.0, self.1 - other.1, self.2 - other.2)
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i32) -> Point {
+        Point(self.0 * factor, self.1 * factor, self.2 * factor)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point(-self.0, -self.1, -self.2)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl From<(i32, i32, i32)> for Point {
+    fn from((x, y, z): (i32, i32, i32)) -> Self {
+        Point(x, y, z)
+    }
+}
+
+impl PointF {
+    pub const ORIGIN: PointF = PointF(0.0, 0.0, 0.0);
+
+    pub fn dot(&self, other: &PointF) -> f64 {
+        self.0 * other.0 + self.1 * other.1 + self.2 * other.2
+    }
+
+    pub fn cross(&self, other: &PointF) -> PointF {
+        PointF(
+            self.1 * other.2 - self.2 * other.1,
+            self.2 * other.0 - self.0 * other.2,
+            self.0 * other.1 - self.1 * other.0,
+        )
+    }
+
+    // The distance from the origin.
+    pub fn length(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn distance_squared(&self, other: &PointF) -> f64 {
+        let d = *self - *other;
+        d.dot(&d)
+    }
+
+    pub fn distance(&self, other: &PointF) -> f64 {
+        (*self - *other).length()
+    }
+
+    // The point in the same direction at distance 1 from the origin;
+    // None for the origin itself, which has no direction.
+    pub fn normalized(&self) -> Option<PointF> {
+        let length = self.length();
+        if length == 0.0 || !length.is_finite() {
+            return None;
+        }
+        Some(PointF(self.0 / length, self.1 / length, self.2 / length))
+    }
+
+    // The nearest integer point, or None when a coordinate is out of the i32
+    // range or not a number.
+    pub fn round(&self) -> Option<Point> {
+        let coordinate = |value: f64| {
+            let value = value.round();
+            (value >= i32::MIN as f64 && value <= i32::MAX as f64).then_some(value as i32)
+        };
+        Some(Point(
+            coordinate(self.0)?,
+            coordinate(self.1)?,
+            coordinate(self.2)?,
+        ))
+    }
+}
+
+impl fmt::Display for PointF {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.0, self.1, self.2)
+    }
+}
+
+impl From<Point> for PointF {
+    fn from(point: Point) -> Self {
+        PointF(point.0 as f64, point.1 as f64, point.2 as f64)
+    }
+}
+
+impl Add for PointF {
+    type Output = PointF;
+
+    fn add(self, other: PointF) -> PointF {
+        PointF(self.0 + other.0, self.1 + other.1, self.2 + other.2)
+    }
+}
+
+impl Sub for PointF {
+    type Output = PointF;
+
+    fn sub(self, other: PointF) -> PointF {
+        PointF(self.0 - other.0, self.1 - other.1, self.2 - other.2)
+    }
+}
+
+impl Mul<f64> for PointF {
+    type Output = PointF;
+
+    fn mul(self, factor: f64) -> PointF {
+        PointF(self.0 * factor, self.1 * factor, self.2 * factor)
+    }
+}
+
+impl Neg for PointF {
+    type Output = PointF;
+
+    fn neg(self) -> PointF {
+        PointF(-self.0, -self.1, -self.2)
+    }
+}
+
+impl AddAssign for PointF {
+    fn add_assign(&mut self, other: PointF) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for PointF {
+    fn sub_assign(&mut self, other: PointF) {
+        *self = *self - other;
+    }
+}
+
+impl BoundingBox {
+    // None for an empty slice, which has no box.
+    pub fn of(points: &[Point]) -> Option<BoundingBox> {
+        let (first, rest) = points.split_first()?;
+        let mut bounds = BoundingBox {
+            min: *first,
+            max: *first,
+        };
+        for point in rest {
+            bounds.min = Point(
+                bounds.min.0.min(point.0),
+                bounds.min.1.min(point.1),
+                bounds.min.2.min(point.2),
+            );
+            bounds.max = Point(
+                bounds.max.0.max(point.0),
+                bounds.max.1.max(point.1),
+                bounds.max.2.max(point.2),
+            );
+        }
+        Some(bounds)
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        (self.min.0..=self.max.0).contains(&point.0)
+            && (self.min.1..=self.max.1).contains(&point.1)
+            && (self.min.2..=self.max.2).contains(&point.2)
+    }
+
+    // (width, height, depth): the distance between the corners along each axis.
+    pub fn size(&self) -> (u32, u32, u32) {
+        (
+            self.max.0.abs_diff(self.min.0),
+            self.max.1.abs_diff(self.min.1),
+            self.max.2.abs_diff(self.min.2),
+        )
+    }
+
+    pub fn center(&self) -> PointF {
+        (PointF::from(self.min) + PointF::from(self.max)) * 0.5
+    }
+}
@@ -0,0 +1,211 @@
+// **** Rectangles ****
+// A Rectangle has a position and a size. The origin is its top-left corner;
+// x grows to the right and y grows downwards, as on a screen. A rectangle
+// covers the points from its origin up to, but not including, origin + size,
+// so two rectangles that only touch along an edge do not overlap.
+//
+// Sizes are u32 and coordinates i32. Anything that can go out of those
+// ranges (area, perimeter, far edges, unions, scaling) is worked out with
+// checked arithmetic and returns GeometryError::Overflow instead of wrapping
+// or panicking. Comparisons between edges are done in i64, where they cannot
+// overflow.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Position {
+    pub fn new(x: i32, y: i32) -> Position {
+        Position { x, y }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rectangle {
+    pub origin: Position,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeometryError {
+    // The named result does not fit in its type.
+    Overflow(&'static str),
+}
+
+impl fmt::Display for GeometryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GeometryError::Overflow(what) => write!(f, "the {what} is too large to represent"),
+        }
+    }
+}
+
+impl std::error::Error for GeometryError {}
+
+// The far edges of a rectangle in i64: (left, top, right, bottom).
+fn edges(rect: &Rectangle) -> (i64, i64, i64, i64) {
+    let left = rect.origin.x as i64;
+    let top = rect.origin.y as i64;
+    (
+        left,
+        top,
+        left + rect.width as i64,
+        top + rect.height as i64,
+    )
+}
+
+// A rectangle from i64 edges, if it fits in the i32 and u32 ranges.
+fn from_edges(
+    left: i64,
+    top: i64,
+    right: i64,
+    bottom: i64,
+    what: &'static str,
+) -> Result<Rectangle, GeometryError> {
+    let overflow = |_| GeometryError::Overflow(what);
+    Ok(Rectangle {
+        origin: Position {
+            x: i32::try_from(left).map_err(overflow)?,
+            y: i32::try_from(top).map_err(overflow)?,
+        },
+        width: u32::try_from(right - left).map_err(overflow)?,
+        height: u32::try_from(bottom - top).map_err(overflow)?,
+    })
+}
+
+// Associated functions are used as constructors.
+impl Rectangle {
+    // A rectangle at (0, 0).
+    pub fn new(width: u32, height: u32) -> Self {
+        Self::at(0, 0, width, height)
+    }
+
+    pub fn at(x: i32, y: i32, width: u32, height: u32) -> Self {
+        Self {
+            origin: Position { x, y },
+            width,
+            height,
+        }
+    }
+
+    pub fn square(size: u32) -> Self {
+        Self::new(size, size)
+    }
+}
+
+// Methods borrow self immutably: none of them change the rectangle,
+// the ones that make a different rectangle return a new one.
+impl Rectangle {
+    pub fn area(&self) -> Result<u32, GeometryError> {
+        self.width
+            .checked_mul(self.height)
+            .ok_or(GeometryError::Overflow("area"))
+    }
+
+    pub fn perimeter(&self) -> Result<u32, GeometryError> {
+        self.width
+            .checked_add(self.height)
+            .and_then(|half| half.checked_mul(2))
+            .ok_or(GeometryError::Overflow("perimeter"))
+    }
+
+    // The x just past the right edge.
+    pub fn right(&self) -> Result<i32, GeometryError> {
+        i32::try_from(edges(self).2).map_err(|_| GeometryError::Overflow("right edge"))
+    }
+
+    // The y just past the bottom edge.
+    pub fn bottom(&self) -> Result<i32, GeometryError> {
+        i32::try_from(edges(self).3).map_err(|_| GeometryError::Overflow("bottom edge"))
+    }
+
+    // A rectangle with no width or no height covers no points.
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    pub fn contains_point(&self, point: Position) -> bool {
+        let (left, top, right, bottom) = edges(self);
+        let (x, y) = (point.x as i64, point.y as i64);
+        left <= x && x < right && top <= y && y < bottom
+    }
+
+    // Whether every point of `other` is inside this rectangle.
+    pub fn contains(&self, other: &Rectangle) -> bool {
+        let (left, top, right, bottom) = edges(self);
+        let (other_left, other_top, other_right, other_bottom) = edges(other);
+        left <= other_left && other_right <= right && top <= other_top && other_bottom <= bottom
+    }
+
+    // Whether the two rectangles share at least one point.
+    pub fn overlaps(&self, other: &Rectangle) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    // The area both rectangles cover, or None if they do not overlap.
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        let (left, top, right, bottom) = edges(self);
+        let (other_left, other_top, other_right, other_bottom) = edges(other);
+        let (left, top) = (left.max(other_left), top.max(other_top));
+        let (right, bottom) = (right.min(other_right), bottom.min(other_bottom));
+        if left >= right || top >= bottom {
+            return None;
+        }
+        // The intersection is inside both rectangles, so it always fits.
+        from_edges(left, top, right, bottom, "intersection").ok()
+    }
+
+    // The smallest rectangle that covers both.
+    pub fn union(&self, other: &Rectangle) -> Result<Rectangle, GeometryError> {
+        let (left, top, right, bottom) = edges(self);
+        let (other_left, other_top, other_right, other_bottom) = edges(other);
+        from_edges(
+            left.min(other_left),
+            top.min(other_top),
+            right.max(other_right),
+            bottom.max(other_bottom),
+            "union",
+        )
+    }
+
+    // Multiplies the size by `factor`, keeping the origin where it is.
+    pub fn scale(&self, factor: u32) -> Result<Rectangle, GeometryError> {
+        let overflow = GeometryError::Overflow("scaled size");
+        Ok(Rectangle {
+            origin: self.origin,
+            width: self.width.checked_mul(factor).ok_or(overflow)?,
+            height: self.height.checked_mul(factor).ok_or(overflow)?,
+        })
+    }
+
+    // The same rectangle turned by 90 degrees about its origin corner.
+    pub fn rotated(&self) -> Rectangle {
+        Rectangle {
+            origin: self.origin,
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    pub fn moved_to(&self, origin: Position) -> Rectangle {
+        Rectangle { origin, ..*self }
+    }
+
+    // Whether `other` fits strictly inside this rectangle, as it is or turned
+    // by 90 degrees. Only the sizes count, not where the rectangles are.
+    pub fn can_hold(&self, other: &Rectangle) -> bool {
+        let fits = |width: u32, height: u32| self.width > width && self.height > height;
+        fits(other.width, other.height) || fits(other.height, other.width)
+    }
+
+    // Like can_hold, but `other` may be exactly as wide or as tall.
+    pub fn can_fit(&self, other: &Rectangle) -> bool {
+        let fits = |width: u32, height: u32| self.width >= width && self.height >= height;
+        fits(other.width, other.height) || fits(other.height, other.width)
+    }
+}
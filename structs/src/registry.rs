@@ -0,0 +1,174 @@
+// **** User Registry ****
+// Keeps every user in memory and makes sure no two users share a username
+// or an email. Both are compared ignoring case, so "Elijah" and "elijah"
+// are the same user. Users are listed in username order.
+//
+// The registry only hands out shared references; every change goes through
+// it so the uniqueness rules cannot be sidestepped.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::session::AuditEvent;
+use crate::user::{User, UserError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    Invalid(UserError),
+    DuplicateUsername(String),
+    DuplicateEmail(String),
+    UnknownUser(String),
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegistryError::Invalid(err) => write!(f, "{err}"),
+            RegistryError::DuplicateUsername(username) => {
+                write!(f, "the username {username} is already taken")
+            }
+            RegistryError::DuplicateEmail(email) => {
+                write!(f, "the email {email} is already registered")
+            }
+            RegistryError::UnknownUser(username) => write!(f, "there is no user {username}"),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+impl From<UserError> for RegistryError {
+    fn from(err: UserError) -> Self {
+        RegistryError::Invalid(err)
+    }
+}
+
+fn key(text: &str) -> String {
+    text.to_lowercase()
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserRegistry {
+    // Keyed by lowercased username.
+    users: BTreeMap<String, User>,
+    // Lowercased email to lowercased username.
+    emails: HashMap<String, String>,
+}
+
+impl UserRegistry {
+    pub fn new() -> UserRegistry {
+        UserRegistry::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.users.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+
+    pub fn add(&mut self, user: User) -> Result<(), RegistryError> {
+        let username = key(user.username());
+        if self.users.contains_key(&username) {
+            return Err(RegistryError::DuplicateUsername(
+                user.username().to_string(),
+            ));
+        }
+        let email = key(user.email());
+        if self.emails.contains_key(&email) {
+            return Err(RegistryError::DuplicateEmail(user.email().to_string()));
+        }
+        self.emails.insert(email, username.clone());
+        self.users.insert(username, user);
+        Ok(())
+    }
+
+    pub fn get(&self, username: &str) -> Option<&User> {
+        self.users.get(&key(username))
+    }
+
+    pub fn find_by_email(&self, email: &str) -> Option<&User> {
+        self.emails
+            .get(&key(email))
+            .and_then(|username| self.users.get(username))
+    }
+
+    pub fn users(&self) -> impl Iterator<Item = &User> {
+        self.users.values()
+    }
+
+    pub fn active_users(&self) -> impl Iterator<Item = &User> {
+        self.users.values().filter(|user| user.is_active())
+    }
+
+    // Checks the new email, and that no other user has it, before changing it.
+    pub fn update_email(&mut self, username: &str, email: &str) -> Result<(), RegistryError> {
+        let old = key(self.user(username)?.email());
+        let new = key(email);
+        if new != old && self.emails.contains_key(&new) {
+            return Err(RegistryError::DuplicateEmail(email.to_string()));
+        }
+        self.user_mut(username)?.change_email(email)?;
+        self.emails.remove(&old);
+        self.emails.insert(new, key(username));
+        Ok(())
+    }
+
+    pub fn sign_in(&mut self, username: &str, now: u64) -> Result<u64, RegistryError> {
+        Ok(self.user_mut(username)?.sign_in(now)?)
+    }
+
+    // Returns the number of failed sign-ins in a row.
+    pub fn failed_sign_in(&mut self, username: &str, now: u64) -> Result<u32, RegistryError> {
+        Ok(self.user_mut(username)?.failed_sign_in(now))
+    }
+
+    // Returns how long the session lasted, in seconds.
+    pub fn sign_out(&mut self, username: &str, now: u64) -> Result<u64, RegistryError> {
+        Ok(self.user_mut(username)?.sign_out(now)?)
+    }
+
+    pub fn unlock(&mut self, username: &str, now: u64) -> Result<(), RegistryError> {
+        self.user_mut(username)?.unlock(now);
+        Ok(())
+    }
+
+    pub fn deactivate(&mut self, username: &str, now: u64) -> Result<(), RegistryError> {
+        self.user_mut(username)?.deactivate(now);
+        Ok(())
+    }
+
+    pub fn activate(&mut self, username: &str, now: u64) -> Result<(), RegistryError> {
+        self.user_mut(username)?.activate(now);
+        Ok(())
+    }
+
+    pub fn signed_in_users(&self) -> impl Iterator<Item = &User> {
+        self.users.values().filter(|user| user.is_signed_in())
+    }
+
+    pub fn audit_log(&self, username: &str) -> Result<&[AuditEvent], RegistryError> {
+        Ok(self.user(username)?.audit_log())
+    }
+
+    pub fn remove(&mut self, username: &str) -> Result<User, RegistryError> {
+        let user = self
+            .users
+            .remove(&key(username))
+            .ok_or_else(|| RegistryError::UnknownUser(username.to_string()))?;
+        self.emails.remove(&key(user.email()));
+        Ok(user)
+    }
+
+    fn user(&self, username: &str) -> Result<&User, RegistryError> {
+        self.get(username)
+            .ok_or_else(|| RegistryError::UnknownUser(username.to_string()))
+    }
+
+    fn user_mut(&mut self, username: &str) -> Result<&mut User, RegistryError> {
+        self.users
+            .get_mut(&key(username))
+            .ok_or_else(|| RegistryError::UnknownUser(username.to_string()))
+    }
+}
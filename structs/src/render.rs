@@ -0,0 +1,183 @@
+// **** Drawing Rectangles ****
+// Draws a set of positioned, labeled rectangles either as an SVG document
+// or as ASCII art for the terminal. Both drawings cover the smallest area
+// that holds every rectangle. Later rectangles are drawn over earlier ones.
+//
+// In ASCII one column stands for `scale` units and one row for twice that,
+// since a terminal cell is about twice as tall as it is wide. The scale is
+// the smallest whole number that makes the drawing fit the given width.
+
+use crate::packing::Packing;
+use crate::rectangle::Rectangle;
+
+// Fill colors for the SVG drawing, used in turn.
+const PALETTE: [&str; 6] = [
+    "#4e79a7", "#f28e2b", "#59a14f", "#e15759", "#76b7b2", "#b07aa1",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shape {
+    pub rect: Rectangle,
+    pub label: String,
+}
+
+impl Shape {
+    pub fn new(rect: Rectangle, label: impl Into<String>) -> Shape {
+        Shape {
+            rect,
+            label: label.into(),
+        }
+    }
+}
+
+impl Packing {
+    // The container and every placed item, labeled with the item's index.
+    pub fn shapes(&self) -> Vec<Shape> {
+        let mut shapes = vec![Shape::new(self.container, "container")];
+        shapes.extend(
+            self.placed
+                .iter()
+                .map(|placement| Shape::new(placement.rect, placement.item.to_string())),
+        );
+        shapes
+    }
+}
+
+// The width of the terminal from $COLUMNS, or 80 when it is not set.
+pub fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|&columns| columns > 0)
+        .unwrap_or(80)
+}
+
+// (left, top, right, bottom) of all shapes together, in i64 so it cannot overflow.
+fn bounds(shapes: &[Shape]) -> Option<(i64, i64, i64, i64)> {
+    shapes
+        .iter()
+        .map(|shape| {
+            let left = shape.rect.origin.x as i64;
+            let top = shape.rect.origin.y as i64;
+            (
+                left,
+                top,
+                left + shape.rect.width as i64,
+                top + shape.rect.height as i64,
+            )
+        })
+        .reduce(|a, b| (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3)))
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn to_svg(shapes: &[Shape]) -> String {
+    let Some((left, top, right, bottom)) = bounds(shapes) else {
+        return String::from(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 0 0\"></svg>\n",
+        );
+    };
+    let span = (right - left).max(bottom - top).max(1);
+    let margin = (span / 20).max(1);
+    let font_size = (span / 30).max(1);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+        left - margin,
+        top - margin,
+        right - left + 2 * margin,
+        bottom - top + 2 * margin
+    );
+    for (index, shape) in shapes.iter().enumerate() {
+        let color = PALETTE[index % PALETTE.len()];
+        let rect = &shape.rect;
+        svg.push_str(&format!(
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{color}\" \
+             fill-opacity=\"0.3\" stroke=\"{color}\" stroke-width=\"1\" \
+             vector-effect=\"non-scaling-stroke\"/>\n",
+            rect.origin.x, rect.origin.y, rect.width, rect.height
+        ));
+        svg.push_str(&format!(
+            "  <text x=\"{}\" y=\"{}\" font-size=\"{font_size}\" font-family=\"monospace\" \
+             text-anchor=\"middle\" dominant-baseline=\"middle\">{}</text>\n",
+            rect.origin.x as i64 + rect.width as i64 / 2,
+            rect.origin.y as i64 + rect.height as i64 / 2,
+            xml_escape(&shape.label)
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// Draws the outlines with '+', '-' and '|' and writes each label inside its
+// rectangle, or on its top edge when the inside is too small.
+// The drawing is at most `width` columns wide and `width` rows tall.
+pub fn to_ascii(shapes: &[Shape], width: usize) -> String {
+    let Some((left, top, right, bottom)) = bounds(shapes) else {
+        return String::new();
+    };
+    let columns = width.max(2) as i64;
+    // The far edge gets a column of its own, so `columns - 1` steps must cover the width.
+    let scale = ((right - left) + columns - 2) / (columns - 1);
+    // A row is two units of scale, so `columns - 1` rows must cover the height.
+    let rows = 2 * (columns - 1);
+    let scale = scale.max(((bottom - top) + rows - 1) / rows).max(1);
+    let row_scale = scale * 2;
+    let column_of = |x: i64| ((x - left) / scale) as usize;
+    let row_of = |y: i64| ((y - top) / row_scale) as usize;
+
+    let cells = |rect: &Rectangle| {
+        let (x, y) = (rect.origin.x as i64, rect.origin.y as i64);
+        (
+            column_of(x),
+            column_of(x + rect.width as i64),
+            row_of(y),
+            row_of(y + rect.height as i64),
+        )
+    };
+
+    let mut grid = vec![vec![' '; column_of(right) + 1]; row_of(bottom) + 1];
+    for shape in shapes {
+        let (c0, c1, r0, r1) = cells(&shape.rect);
+        for row in [r0, r1] {
+            grid[row][c0..=c1].fill('-');
+        }
+        for row in &mut grid[r0..=r1] {
+            row[c0] = '|';
+            row[c1] = '|';
+        }
+        for (row, column) in [(r0, c0), (r0, c1), (r1, c0), (r1, c1)] {
+            grid[row][column] = '+';
+        }
+    }
+    // Labels go in once every outline is drawn, and stop at the next line
+    // they would cross, so no label hides an edge.
+    for shape in shapes {
+        let (c0, c1, r0, r1) = cells(&shape.rect);
+        let label_row = if r1 > r0 + 1 { r0 + 1 } else { r0 };
+        let row = &mut grid[label_row];
+        let cells = row[c0 + 1..c1.max(c0 + 1)]
+            .iter()
+            .take_while(|&&cell| cell == ' ' || label_row == r0 && cell == '-')
+            .count();
+        for (offset, c) in shape.label.chars().take(cells).enumerate() {
+            row[c0 + 1 + offset] = c;
+        }
+    }
+
+    let mut text = String::new();
+    for row in grid {
+        let line: String = row.into_iter().collect();
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text.push_str(&format!(
+        "(1 column = {scale} units, 1 row = {row_scale} units)\n"
+    ));
+    text
+}
@@ -0,0 +1,55 @@
+// **** Sessions ****
+// What a User remembers about signing in: when each session started and
+// ended, and an audit log of everything that happened to the account.
+//
+// Every call that changes a session takes `now`, the time of the call in
+// seconds since the unix epoch. Callers pick the clock, so a test can sign
+// in at second 10 and out at second 70.
+
+use std::fmt;
+
+// Failed sign-ins in a row before an account is locked, unless the user
+// was built with another limit.
+pub const DEFAULT_MAX_FAILED_SIGN_INS: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuditKind {
+    SignedIn,
+    // How long the session that ended had lasted, in seconds.
+    SignedOut { session_seconds: u64 },
+    // The sign-in was refused: wrong credentials, a locked or a deactivated account.
+    SignInFailed,
+    Locked,
+    Unlocked,
+    Deactivated,
+    Activated,
+}
+
+impl fmt::Display for AuditKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AuditKind::SignedIn => write!(f, "signed in"),
+            AuditKind::SignedOut { session_seconds } => {
+                write!(f, "signed out after {session_seconds}s")
+            }
+            AuditKind::SignInFailed => write!(f, "sign-in failed"),
+            AuditKind::Locked => write!(f, "locked"),
+            AuditKind::Unlocked => write!(f, "unlocked"),
+            AuditKind::Deactivated => write!(f, "deactivated"),
+            AuditKind::Activated => write!(f, "activated"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AuditEvent {
+    // Seconds since the unix epoch.
+    pub at: u64,
+    pub kind: AuditKind,
+}
+
+impl fmt::Display for AuditEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.at, self.kind)
+    }
+}
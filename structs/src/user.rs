@@ -0,0 +1,394 @@
+// **** Users ****
+// A User is only ever made through UserBuilder, which checks the username
+// and the email before handing the user out. The fields are private,
+// so a user that exists is always a valid one.
+//
+// Usernames are 3 to 32 characters long, start with a letter and contain
+// only ASCII letters, digits, '_', '-' and '.'.
+// Emails are checked for their shape (local@domain.tld), not for whether
+// the mailbox exists.
+//
+// A user has at most one session open at a time. Checking credentials is
+// up to the caller, which reports a refused attempt with failed_sign_in;
+// after max_failed_sign_ins of them in a row the account is locked until
+// it is unlocked. Every sign-in, sign-out, lock and change of state goes
+// into the user's audit log.
+
+use std::fmt;
+
+use crate::session::{AuditEvent, AuditKind, DEFAULT_MAX_FAILED_SIGN_INS};
+
+pub const USERNAME_MIN_LEN: usize = 3;
+pub const USERNAME_MAX_LEN: usize = 32;
+pub const EMAIL_MAX_LEN: usize = 254;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct User {
+    username: String,
+    email: String,
+    sign_in_count: u64,
+    active: bool,
+    // 0 means the account is never locked.
+    max_failed_sign_ins: u32,
+    failed_sign_ins: u32,
+    locked: bool,
+    session_start: Option<u64>,
+    last_seen: Option<u64>,
+    audit_log: Vec<AuditEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserError {
+    MissingUsername,
+    MissingEmail,
+    UsernameTooShort { length: usize },
+    UsernameTooLong { length: usize },
+    UsernameStartsWithNonLetter(char),
+    UsernameInvalidChar(char),
+    // `reason` says which part of the address is wrong.
+    InvalidEmail { email: String, reason: &'static str },
+    // A deactivated user cannot sign in.
+    Inactive(String),
+    // Too many failed sign-ins; the account must be unlocked first.
+    Locked(String),
+    NotSignedIn(String),
+}
+
+impl fmt::Display for UserError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UserError::MissingUsername => write!(f, "a user needs a username"),
+            UserError::MissingEmail => write!(f, "a user needs an email address"),
+            UserError::UsernameTooShort { length } => write!(
+                f,
+                "username has {length} characters, at least {USERNAME_MIN_LEN} are needed"
+            ),
+            UserError::UsernameTooLong { length } => write!(
+                f,
+                "username has {length} characters, at most {USERNAME_MAX_LEN} are allowed"
+            ),
+            UserError::UsernameStartsWithNonLetter(c) => {
+                write!(f, "username must start with a letter, not '{c}'")
+            }
+            UserError::UsernameInvalidChar(c) => {
+                write!(f, "username may not contain '{c}'")
+            }
+            UserError::InvalidEmail { email, reason } => {
+                write!(f, "'{email}' is not a valid email address: {reason}")
+            }
+            UserError::Inactive(username) => write!(f, "user {username} is deactivated"),
+            UserError::Locked(username) => {
+                write!(
+                    f,
+                    "user {username} is locked after too many failed sign-ins"
+                )
+            }
+            UserError::NotSignedIn(username) => write!(f, "user {username} is not signed in"),
+        }
+    }
+}
+
+impl std::error::Error for UserError {}
+
+pub fn validate_username(username: &str) -> Result<(), UserError> {
+    let length = username.chars().count();
+    if length < USERNAME_MIN_LEN {
+        return Err(UserError::UsernameTooShort { length });
+    }
+    if length > USERNAME_MAX_LEN {
+        return Err(UserError::UsernameTooLong { length });
+    }
+    let first = username.chars().next().expect("username is not empty");
+    if !first.is_ascii_alphabetic() {
+        return Err(UserError::UsernameStartsWithNonLetter(first));
+    }
+    match username
+        .chars()
+        .find(|&c| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')))
+    {
+        Some(c) => Err(UserError::UsernameInvalidChar(c)),
+        None => Ok(()),
+    }
+}
+
+pub fn validate_email(email: &str) -> Result<(), UserError> {
+    let invalid = |reason| {
+        Err(UserError::InvalidEmail {
+            email: email.to_string(),
+            reason,
+        })
+    };
+    if email.len() > EMAIL_MAX_LEN {
+        return invalid("it is too long");
+    }
+    let Some((local, domain)) = email.split_once('@') else {
+        return invalid("there is no '@'");
+    };
+    if domain.contains('@') {
+        return invalid("there is more than one '@'");
+    }
+
+    // The part before the '@'.
+    if local.is_empty() || local.len() > 64 {
+        return invalid("the part before '@' must have 1 to 64 characters");
+    }
+    if local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+        return invalid("dots in the part before '@' must separate words");
+    }
+    let allowed = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~.-".contains(c);
+    if !local.chars().all(allowed) {
+        return invalid("the part before '@' has a character that is not allowed");
+    }
+
+    // The domain: at least two labels, the last one alphabetic.
+    let labels: Vec<&str> = domain.split('.').collect();
+    if labels.len() < 2 {
+        return invalid("the domain needs a dot, as in example.com");
+    }
+    for label in &labels {
+        if label.is_empty() || label.len() > 63 {
+            return invalid("every part of the domain must have 1 to 63 characters");
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return invalid("a part of the domain may not start or end with '-'");
+        }
+        if !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return invalid("the domain has a character that is not allowed");
+        }
+    }
+    let top_level = labels[labels.len() - 1];
+    if top_level.len() < 2 || !top_level.chars().all(|c| c.is_ascii_alphabetic()) {
+        return invalid("the domain must end in a name of letters, as in .com");
+    }
+    Ok(())
+}
+
+impl User {
+    pub fn builder() -> UserBuilder {
+        UserBuilder::default()
+    }
+
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+
+    pub fn sign_in_count(&self) -> u64 {
+        self.sign_in_count
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    // Failed sign-ins since the last successful one.
+    pub fn failed_sign_ins(&self) -> u32 {
+        self.failed_sign_ins
+    }
+
+    pub fn is_signed_in(&self) -> bool {
+        self.session_start.is_some()
+    }
+
+    // The last sign-in, sign-out or activity, None if the user never signed in.
+    pub fn last_seen(&self) -> Option<u64> {
+        self.last_seen
+    }
+
+    // How long the open session has lasted at `now`, in seconds.
+    pub fn session_duration(&self, now: u64) -> Option<u64> {
+        self.session_start.map(|start| now.saturating_sub(start))
+    }
+
+    // Oldest event first.
+    pub fn audit_log(&self) -> &[AuditEvent] {
+        &self.audit_log
+    }
+
+    fn record(&mut self, at: u64, kind: AuditKind) {
+        self.audit_log.push(AuditEvent { at, kind });
+    }
+
+    // Opens a session, counts the sign-in and returns the new count.
+    // A session that was still open is signed out first.
+    pub fn sign_in(&mut self, now: u64) -> Result<u64, UserError> {
+        if !self.active {
+            self.record(now, AuditKind::SignInFailed);
+            return Err(UserError::Inactive(self.username.clone()));
+        }
+        if self.locked {
+            self.record(now, AuditKind::SignInFailed);
+            return Err(UserError::Locked(self.username.clone()));
+        }
+        self.end_session(now);
+        // Imported counts can be as large as a u64 holds, so the count stops there.
+        self.sign_in_count = self.sign_in_count.saturating_add(1);
+        self.failed_sign_ins = 0;
+        self.session_start = Some(now);
+        self.last_seen = Some(now);
+        self.record(now, AuditKind::SignedIn);
+        Ok(self.sign_in_count)
+    }
+
+    // Records a refused sign-in, locking the account when it is one too many.
+    // Returns the number of failed sign-ins in a row.
+    pub fn failed_sign_in(&mut self, now: u64) -> u32 {
+        self.record(now, AuditKind::SignInFailed);
+        if self.locked {
+            return self.failed_sign_ins;
+        }
+        self.failed_sign_ins += 1;
+        if self.max_failed_sign_ins > 0 && self.failed_sign_ins >= self.max_failed_sign_ins {
+            self.locked = true;
+            self.end_session(now);
+            self.record(now, AuditKind::Locked);
+        }
+        self.failed_sign_ins
+    }
+
+    // Closes the open session and returns how long it lasted, in seconds.
+    pub fn sign_out(&mut self, now: u64) -> Result<u64, UserError> {
+        self.end_session(now)
+            .ok_or_else(|| UserError::NotSignedIn(self.username.clone()))
+    }
+
+    fn end_session(&mut self, now: u64) -> Option<u64> {
+        let start = self.session_start.take()?;
+        let session_seconds = now.saturating_sub(start);
+        self.last_seen = Some(now);
+        self.record(now, AuditKind::SignedOut { session_seconds });
+        Some(session_seconds)
+    }
+
+    // Marks the signed-in user as seen at `now` without opening a new session.
+    pub fn record_activity(&mut self, now: u64) -> Result<(), UserError> {
+        if !self.is_signed_in() {
+            return Err(UserError::NotSignedIn(self.username.clone()));
+        }
+        self.last_seen = Some(now);
+        Ok(())
+    }
+
+    pub fn unlock(&mut self, now: u64) {
+        self.failed_sign_ins = 0;
+        if self.locked {
+            self.locked = false;
+            self.record(now, AuditKind::Unlocked);
+        }
+    }
+
+    // Replaces the email after checking the new one.
+    pub fn change_email(&mut self, email: impl Into<String>) -> Result<(), UserError> {
+        let email = email.into();
+        validate_email(&email)?;
+        self.email = email;
+        Ok(())
+    }
+
+    // Also signs the user out.
+    pub fn deactivate(&mut self, now: u64) {
+        self.end_session(now);
+        if self.active {
+            self.active = false;
+            self.record(now, AuditKind::Deactivated);
+        }
+    }
+
+    pub fn activate(&mut self, now: u64) {
+        if !self.active {
+            self.active = true;
+            self.record(now, AuditKind::Activated);
+        }
+    }
+}
+
+impl fmt::Display for User {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} <{}>", self.username, self.email)?;
+        if !self.active {
+            write!(f, " (deactivated)")?;
+        }
+        if self.locked {
+            write!(f, " (locked)")?;
+        }
+        Ok(())
+    }
+}
+
+// **** Building a User ****
+// Username and email are required. A new user is active and has
+// not signed in yet unless told otherwise.
+#[derive(Debug, Clone)]
+pub struct UserBuilder {
+    username: Option<String>,
+    email: Option<String>,
+    sign_in_count: u64,
+    active: bool,
+    max_failed_sign_ins: u32,
+}
+
+impl Default for UserBuilder {
+    fn default() -> Self {
+        UserBuilder {
+            username: None,
+            email: None,
+            sign_in_count: 0,
+            active: true,
+            max_failed_sign_ins: DEFAULT_MAX_FAILED_SIGN_INS,
+        }
+    }
+}
+
+impl UserBuilder {
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    pub fn sign_in_count(mut self, count: u64) -> Self {
+        self.sign_in_count = count;
+        self
+    }
+
+    pub fn active(mut self, active: bool) -> Self {
+        self.active = active;
+        self
+    }
+
+    // 0 turns locking off.
+    pub fn max_failed_sign_ins(mut self, attempts: u32) -> Self {
+        self.max_failed_sign_ins = attempts;
+        self
+    }
+
+    pub fn build(self) -> Result<User, UserError> {
+        let username = self.username.ok_or(UserError::MissingUsername)?;
+        let email = self.email.ok_or(UserError::MissingEmail)?;
+        validate_username(&username)?;
+        validate_email(&email)?;
+        Ok(User {
+            username,
+            email,
+            sign_in_count: self.sign_in_count,
+            active: self.active,
+            max_failed_sign_ins: self.max_failed_sign_ins,
+            failed_sign_ins: 0,
+            locked: false,
+            session_start: None,
+            last_seen: None,
+            audit_log: Vec::new(),
+        })
+    }
+}
@@ -0,0 +1,127 @@
+use structs::color::{Color, ColorError, Hsl, Hsv};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn parses_and_prints_hex() {
+    assert_eq!(Color::from_hex("#00a0ff"), Ok(Color(0, 160, 255)));
+    assert_eq!("00A0FF".parse(), Ok(Color(0, 160, 255)));
+    assert_eq!(Color::from_hex("#0af"), Ok(Color(0, 170, 255)));
+    assert_eq!(Color(0, 160, 255).to_hex(), "#00a0ff");
+    assert_eq!(Color(0, 160, 255).to_string(), "#00a0ff");
+    for bad in ["", "#", "#12345", "#1234567", "#gg0000", "#+1+2+3"] {
+        assert_eq!(
+            Color::from_hex(bad),
+            Err(ColorError::InvalidHex(bad.into())),
+            "{bad}"
+        );
+    }
+}
+
+#[test]
+fn checks_raw_channels() {
+    assert_eq!(Color::try_from((0, 128, 255)), Ok(Color(0, 128, 255)));
+    assert_eq!(
+        Color::try_from((0, 256, 0)),
+        Err(ColorError::ChannelOutOfRange {
+            channel: "green",
+            value: 256
+        })
+    );
+    assert_eq!(
+        Color::from_channels(-1, 0, 0),
+        Err(ColorError::ChannelOutOfRange {
+            channel: "red",
+            value: -1
+        })
+    );
+}
+
+#[test]
+fn checks_hsv_and_hsl_components() {
+    assert!(matches!(
+        Hsv::new(0.0, 1.5, 1.0),
+        Err(ColorError::ComponentOutOfRange {
+            component: "saturation",
+            ..
+        })
+    ));
+    assert!(matches!(
+        Hsl::new(f64::NAN, 0.5, 0.5),
+        Err(ColorError::ComponentOutOfRange {
+            component: "hue",
+            ..
+        })
+    ));
+    assert_eq!(Hsv::new(-30.0, 1.0, 1.0).unwrap().hue, 330.0);
+    assert_eq!(Hsl::new(720.0, 1.0, 0.5).unwrap().hue, 0.0);
+}
+
+#[test]
+fn converts_known_colors() {
+    let orange = Color(255, 128, 0);
+    let hsv = orange.to_hsv();
+    assert!(
+        close(hsv.hue, 128.0 / 255.0 * 60.0) && close(hsv.saturation, 1.0) && close(hsv.value, 1.0)
+    );
+    let hsl = orange.to_hsl();
+    assert!(close(hsl.saturation, 1.0) && close(hsl.lightness, 0.5));
+
+    assert_eq!(
+        Hsv::new(120.0, 1.0, 1.0).unwrap().to_rgb(),
+        Color(0, 255, 0)
+    );
+    assert_eq!(
+        Hsl::new(240.0, 1.0, 0.5).unwrap().to_rgb(),
+        Color(0, 0, 255)
+    );
+    assert_eq!(
+        Hsl::new(0.0, 0.0, 0.5).unwrap().to_rgb(),
+        Color(128, 128, 128)
+    );
+    assert_eq!(Color(0, 0, 0).to_hsl().saturation, 0.0);
+}
+
+#[test]
+fn every_color_survives_a_round_trip() {
+    for red in (0..=255).step_by(5) {
+        for green in (0..=255).step_by(3) {
+            for blue in (0..=255).step_by(7) {
+                let color = Color(red, green, blue);
+                assert_eq!(Color::from(color.to_hsv()), color);
+                assert_eq!(Color::from(color.to_hsl()), color);
+                assert_eq!(Color::from(color.to_hsv().to_hsl()), color);
+                assert_eq!(Color::from(color.to_hsl().to_hsv()), color);
+            }
+        }
+    }
+}
+
+#[test]
+fn blends_between_colors() {
+    let black = Color::BLACK;
+    let white = Color::WHITE;
+    assert_eq!(black.blend(&white, 0.0), black);
+    assert_eq!(black.blend(&white, 1.0), white);
+    assert_eq!(black.blend(&white, 0.5), Color(128, 128, 128));
+    assert_eq!(black.blend(&white, 7.0), white);
+    assert_eq!(
+        Color(200, 0, 100).blend(&Color(0, 100, 100), 0.25),
+        Color(150, 25, 100)
+    );
+}
+
+#[test]
+fn contrast_ratio_follows_wcag() {
+    assert!(close(Color::BLACK.contrast_ratio(&Color::WHITE), 21.0));
+    assert!(close(Color::WHITE.contrast_ratio(&Color::BLACK), 21.0));
+    assert!(close(
+        Color(0, 160, 255).contrast_ratio(&Color(0, 160, 255)),
+        1.0
+    ));
+    // #777777 on white is a well known near miss for AA.
+    assert!(!Color(0x77, 0x77, 0x77).is_readable_on(&Color::WHITE));
+    assert!(Color(0x76, 0x76, 0x76).is_readable_on(&Color::WHITE));
+}
@@ -0,0 +1,181 @@
+use structs::import_export::{Format, ImportError, RowError, RowProblem};
+use structs::registry::{RegistryError, UserRegistry};
+use structs::user::{User, UserError};
+
+fn registry() -> UserRegistry {
+    let mut registry = UserRegistry::new();
+    let elijah = User::builder()
+        .username("Elijah")
+        .email("elijahgathang@gmail.com")
+        .sign_in_count(4)
+        .active(false)
+        .build()
+        .unwrap();
+    registry.add(elijah).unwrap();
+    registry
+        .add(
+            User::builder()
+                .username("quote.maker")
+                .email("o'hara+x@example.com")
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+    registry
+}
+
+#[test]
+fn json_and_csv_round_trip() {
+    let registry = registry();
+    for format in [Format::Json, Format::Csv] {
+        let mut restored = UserRegistry::new();
+        let report = restored.import(&registry.export(format), format).unwrap();
+        assert_eq!(report.imported, 2, "{format:?}");
+        assert!(report.errors.is_empty(), "{format:?}");
+        assert_eq!(restored, registry, "{format:?}");
+    }
+}
+
+#[test]
+fn exports_csv_with_a_header() {
+    assert_eq!(
+        registry().to_csv(),
+        "username,email,sign_in_count,active\n\
+         Elijah,elijahgathang@gmail.com,4,false\n\
+         quote.maker,o'hara+x@example.com,0,true\n"
+    );
+}
+
+#[test]
+fn csv_import_reports_bad_rows_and_keeps_the_rest() {
+    let csv = "email,username\n\
+               a@example.com,alice\n\
+               not-an-email,bob\n\
+               \n\
+               c@example.com,Alice\n\
+               \"d@example.com\",dave\n";
+    let mut registry = UserRegistry::new();
+    let report = registry.import_csv(csv).unwrap();
+    assert_eq!(report.imported, 2);
+    assert_eq!(report.errors.len(), 2);
+    assert!(matches!(
+        &report.errors[0],
+        RowError {
+            row: 3,
+            problem: RowProblem::Rejected(RegistryError::Invalid(UserError::InvalidEmail { .. }))
+        }
+    ));
+    assert_eq!(
+        report.errors[1],
+        RowError {
+            row: 5,
+            problem: RowProblem::Rejected(RegistryError::DuplicateUsername("Alice".into()))
+        }
+    );
+    assert!(registry.get("dave").unwrap().is_active());
+}
+
+#[test]
+fn json_import_reports_bad_rows_and_keeps_the_rest() {
+    let json = r#"[
+        {"username": "alice", "email": "a@example.com", "sign_in_count": 2, "active": false},
+        {"username": "bob"},
+        {"username": "carol", "email": "a@example.com"},
+        {"username": "dave", "email": "d@example.com", "sign_in_count": -1},
+        "erin",
+        {"username": "frank", "email": "fé@example.com"},
+        {"username": "gina", "email": "g@example.com", "extra": [1, {"x": null}]}
+    ]"#;
+    let mut registry = UserRegistry::new();
+    let report = registry.import_json(json).unwrap();
+    assert_eq!(report.imported, 2);
+    let problems: Vec<(usize, &RowProblem)> = report
+        .errors
+        .iter()
+        .map(|error| (error.row, &error.problem))
+        .collect();
+    assert_eq!(problems[0], (2, &RowProblem::MissingField("email")));
+    assert_eq!(
+        problems[1],
+        (
+            3,
+            &RowProblem::Rejected(RegistryError::DuplicateEmail("a@example.com".into()))
+        )
+    );
+    assert_eq!(
+        problems[2],
+        (
+            4,
+            &RowProblem::InvalidField {
+                field: "sign_in_count",
+                value: "-1".into()
+            }
+        )
+    );
+    assert_eq!(problems[3], (5, &RowProblem::NotAnObject));
+    assert_eq!(problems[4].0, 6);
+    let alice = registry.get("alice").unwrap();
+    assert_eq!(alice.sign_in_count(), 2);
+    assert!(!alice.is_active());
+}
+
+#[test]
+fn rejects_files_that_are_not_json_or_csv() {
+    let mut registry = UserRegistry::new();
+    assert!(matches!(
+        registry.import_json("[{\"username\": \"alice\",]"),
+        Err(ImportError::Json { line: 1, .. })
+    ));
+    assert!(matches!(
+        registry.import_json("{\"username\": \"alice\"}"),
+        Err(ImportError::Json { .. })
+    ));
+    assert!(matches!(
+        registry.import_csv("name,email\nalice,a@example.com\n"),
+        Err(ImportError::Csv { line: 1, .. })
+    ));
+    assert!(matches!(
+        registry.import_csv("username,email\n\"alice,a@example.com\n"),
+        Err(ImportError::Csv { line: 2, .. })
+    ));
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn json_numbers_follow_the_json_grammar() {
+    let mut registry = UserRegistry::new();
+    for number in ["+1", "01", "-", "1.", ".5", "1e", "1e+", "2E-"] {
+        let json = format!(r#"[{{"username": "alice", "email": "a@example.com", "x": {number}}}]"#);
+        assert!(
+            matches!(registry.import_json(&json), Err(ImportError::Json { .. })),
+            "{number}"
+        );
+    }
+    assert!(registry.is_empty());
+
+    let json =
+        r#"[{"username": "alice", "email": "a@example.com", "x": [0, -0.5, 10, 1.25e+3, 2E-1]}]"#;
+    assert_eq!(registry.import_json(json).unwrap().imported, 1);
+}
+
+#[test]
+fn json_nested_too_deep_is_refused() {
+    let mut registry = UserRegistry::new();
+    let nested = |depth: usize| {
+        format!(
+            r#"[{{"username": "alice", "email": "a@example.com", "x": {}{}}}]"#,
+            "[".repeat(depth),
+            "]".repeat(depth)
+        )
+    };
+    // The list of users and its row are two levels already.
+    assert_eq!(registry.import_json(&nested(62)).unwrap().imported, 1);
+    assert!(matches!(
+        registry.import_json(&nested(63)),
+        Err(ImportError::Json { line: 1, .. })
+    ));
+    assert!(matches!(
+        registry.import_json(&"[".repeat(100_000)),
+        Err(ImportError::Json { .. })
+    ));
+}
@@ -0,0 +1,83 @@
+use structs::packing::{Packing, pack_shelves};
+use structs::rectangle::Rectangle;
+
+// Every placed item is inside the container, has the size of its item
+// (maybe turned) and overlaps no other placed item.
+fn assert_valid(packing: &Packing, items: &[Rectangle]) {
+    for (n, placement) in packing.placed.iter().enumerate() {
+        let item = items[placement.item];
+        let expected = if placement.rotated {
+            item.rotated()
+        } else {
+            item
+        };
+        assert_eq!(
+            (placement.rect.width, placement.rect.height),
+            (expected.width, expected.height)
+        );
+        assert!(packing.container.contains(&placement.rect), "{placement:?}");
+        for other in &packing.placed[n + 1..] {
+            assert!(
+                !placement.rect.overlaps(&other.rect),
+                "{placement:?} {other:?}"
+            );
+        }
+    }
+    assert_eq!(
+        packing.placed.len() + packing.unplaced.len(),
+        items.len(),
+        "every item is placed or unplaced"
+    );
+}
+
+#[test]
+fn fills_a_container_exactly() {
+    let container = Rectangle::new(10, 10);
+    let items = [
+        Rectangle::new(10, 5),
+        Rectangle::new(5, 5),
+        Rectangle::new(5, 5),
+    ];
+    let packing = pack_shelves(&container, &items);
+    assert_valid(&packing, &items);
+    assert!(packing.unplaced.is_empty());
+    assert_eq!(packing.utilization(), 100.0);
+}
+
+#[test]
+fn turns_items_that_only_fit_on_their_side() {
+    let container = Rectangle::new(10, 4);
+    let items = [Rectangle::new(3, 8)];
+    let packing = pack_shelves(&container, &items);
+    assert_valid(&packing, &items);
+    assert!(packing.placed[0].rotated);
+    assert_eq!(packing.placed[0].rect, Rectangle::new(8, 3));
+}
+
+#[test]
+fn reports_items_that_do_not_fit() {
+    let container = Rectangle::at(100, -50, 10, 10);
+    let items = [
+        Rectangle::new(11, 1),
+        Rectangle::new(6, 6),
+        Rectangle::new(6, 6),
+        Rectangle::new(4, 4),
+    ];
+    let packing = pack_shelves(&container, &items);
+    assert_valid(&packing, &items);
+    assert_eq!(packing.unplaced, [0, 2]);
+    assert_eq!(packing.placed[0].rect, Rectangle::at(100, -50, 6, 6));
+    assert_eq!(packing.used_area(), 52);
+    assert_eq!(packing.utilization(), 52.0);
+}
+
+#[test]
+fn packs_many_items_without_overlap() {
+    let container = Rectangle::new(100, 80);
+    let items: Vec<Rectangle> = (0..60)
+        .map(|n| Rectangle::new(3 + (n * 7) % 17, 2 + (n * 5) % 11))
+        .collect();
+    let packing = pack_shelves(&container, &items);
+    assert_valid(&packing, &items);
+    assert!(packing.utilization() > 50.0);
+}
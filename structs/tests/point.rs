@@ -0,0 +1,100 @@
+use structs::point::{BoundingBox, Point, PointF};
+use structs::rectangle::GeometryError;
+
+#[test]
+fn operators_work_per_coordinate() {
+    let a = Point(1, 2, 3);
+    let b = Point(4, -5, 6);
+    assert_eq!(a + b, Point(5, -3, 9));
+    assert_eq!(a - b, Point(-3, 7, -3));
+    assert_eq!(a * 3, Point(3, 6, 9));
+    assert_eq!(-a, Point(-1, -2, -3));
+
+    let mut c = a;
+    c += b;
+    c -= a;
+    assert_eq!(c, b);
+
+    // Destructuring still works as for any tuple struct.
+    let Point(x, y, z) = a + b;
+    assert_eq!((x, y, z), (5, -3, 9));
+}
+
+#[test]
+fn checked_operations_report_overflow() {
+    let big = Point(i32::MAX, 0, 0);
+    assert_eq!(big.checked_add(&Point(0, 1, 1)), Ok(Point(i32::MAX, 1, 1)));
+    assert_eq!(
+        big.checked_add(&Point(1, 0, 0)),
+        Err(GeometryError::Overflow("sum"))
+    );
+    assert_eq!(
+        Point(0, i32::MIN, 0).checked_sub(&Point(0, 1, 0)),
+        Err(GeometryError::Overflow("difference"))
+    );
+    assert_eq!(
+        big.checked_scale(2),
+        Err(GeometryError::Overflow("scaled point"))
+    );
+}
+
+#[test]
+fn dot_and_cross_products() {
+    let x = Point(1, 0, 0);
+    let y = Point(0, 1, 0);
+    assert_eq!(x.dot(&y), 0);
+    assert_eq!(Point(1, 2, 3).dot(&Point(4, 5, 6)), 32);
+    assert_eq!(x.cross(&y), Ok(Point(0, 0, 1)));
+    assert_eq!(y.cross(&x), Ok(Point(0, 0, -1)));
+
+    let big = Point(i32::MAX, i32::MAX, i32::MAX);
+    assert_eq!(big.dot(&big), 3 * (i32::MAX as i128).pow(2));
+    assert_eq!(
+        big.cross(&Point(i32::MIN, 0, 0)),
+        Err(GeometryError::Overflow("cross product"))
+    );
+}
+
+#[test]
+fn distances() {
+    let a = Point(1, 2, 3);
+    let b = Point(4, 6, 3);
+    assert_eq!(a.distance_squared(&b), 25);
+    assert_eq!(a.distance(&b), 5.0);
+    assert_eq!(
+        Point(i32::MIN, i32::MIN, i32::MIN).distance_squared(&Point(i32::MAX, i32::MAX, i32::MAX)),
+        3 * (u32::MAX as u128).pow(2)
+    );
+}
+
+#[test]
+fn float_points() {
+    let p = PointF::from(Point(3, 0, 4));
+    assert_eq!(p.length(), 5.0);
+    assert_eq!(p.normalized(), Some(PointF(0.6, 0.0, 0.8)));
+    assert_eq!(PointF::ORIGIN.normalized(), None);
+    assert_eq!(
+        PointF(1.0, 0.0, 0.0).cross(&PointF(0.0, 1.0, 0.0)),
+        PointF(0.0, 0.0, 1.0)
+    );
+    assert_eq!(PointF(1.4, -2.6, 0.5).round(), Some(Point(1, -3, 1)));
+    assert_eq!(PointF(1e10, 0.0, 0.0).round(), None);
+    assert_eq!(PointF(f64::NAN, 0.0, 0.0).round(), None);
+}
+
+#[test]
+fn bounding_box_holds_every_point() {
+    assert_eq!(BoundingBox::of(&[]), None);
+
+    let points = [Point(1, 5, -2), Point(-3, 2, 0), Point(4, -1, 7)];
+    let bounds = BoundingBox::of(&points).unwrap();
+    assert_eq!(bounds.min, Point(-3, -1, -2));
+    assert_eq!(bounds.max, Point(4, 5, 7));
+    assert!(points.iter().all(|point| bounds.contains(point)));
+    assert!(!bounds.contains(&Point(5, 0, 0)));
+    assert_eq!(bounds.size(), (7, 6, 9));
+    assert_eq!(bounds.center(), PointF(0.5, 2.0, 2.5));
+
+    let single = BoundingBox::of(&[Point(i32::MIN, 0, i32::MAX)]).unwrap();
+    assert_eq!(single.size(), (0, 0, 0));
+}
@@ -0,0 +1,85 @@
+use structs::rectangle::{GeometryError, Position, Rectangle};
+
+#[test]
+fn area_and_perimeter_report_overflow() {
+    let rect = Rectangle::new(30, 50);
+    assert_eq!(rect.area(), Ok(1500));
+    assert_eq!(rect.perimeter(), Ok(160));
+
+    let huge = Rectangle::new(u32::MAX, 2);
+    assert_eq!(huge.area(), Err(GeometryError::Overflow("area")));
+    assert_eq!(huge.perimeter(), Err(GeometryError::Overflow("perimeter")));
+    assert_eq!(huge.scale(2), Err(GeometryError::Overflow("scaled size")));
+}
+
+#[test]
+fn square_has_equal_sides() {
+    assert_eq!(Rectangle::square(3), Rectangle::new(3, 3));
+}
+
+#[test]
+fn far_edges_are_checked() {
+    let rect = Rectangle::at(-5, 10, 20, 5);
+    assert_eq!(rect.right(), Ok(15));
+    assert_eq!(rect.bottom(), Ok(15));
+    assert_eq!(
+        Rectangle::at(i32::MAX, 0, 1, 1).right(),
+        Err(GeometryError::Overflow("right edge"))
+    );
+}
+
+#[test]
+fn contains_points_up_to_but_not_on_the_far_edges() {
+    let rect = Rectangle::at(0, 0, 10, 10);
+    assert!(rect.contains_point(Position::new(0, 0)));
+    assert!(rect.contains_point(Position::new(9, 9)));
+    assert!(!rect.contains_point(Position::new(10, 5)));
+    assert!(!rect.contains_point(Position::new(-1, 5)));
+    assert!(!Rectangle::new(0, 10).contains_point(Position::new(0, 0)));
+}
+
+#[test]
+fn intersection_and_overlap() {
+    let a = Rectangle::at(0, 0, 10, 10);
+    let b = Rectangle::at(5, 5, 10, 10);
+    assert_eq!(a.intersection(&b), Some(Rectangle::at(5, 5, 5, 5)));
+    assert!(a.overlaps(&b));
+
+    // Touching along an edge is not overlapping.
+    let beside = Rectangle::at(10, 0, 5, 5);
+    assert_eq!(a.intersection(&beside), None);
+    assert!(!a.overlaps(&beside));
+}
+
+#[test]
+fn union_covers_both_and_reports_overflow() {
+    let a = Rectangle::at(0, 0, 10, 10);
+    let b = Rectangle::at(-5, 20, 5, 5);
+    let union = a.union(&b).unwrap();
+    assert_eq!(union, Rectangle::at(-5, 0, 15, 25));
+    assert!(union.contains(&a) && union.contains(&b));
+
+    let far_left = Rectangle::at(i32::MIN, 0, 1, 1);
+    let far_right = Rectangle::at(i32::MAX, 0, u32::MAX, 1);
+    assert_eq!(
+        far_left.union(&far_right),
+        Err(GeometryError::Overflow("union"))
+    );
+}
+
+#[test]
+fn scaling_keeps_the_origin() {
+    let rect = Rectangle::at(3, 4, 10, 20);
+    assert_eq!(rect.scale(3), Ok(Rectangle::at(3, 4, 30, 60)));
+}
+
+#[test]
+fn can_hold_considers_rotation() {
+    let rect1 = Rectangle::new(30, 50);
+    assert!(rect1.can_hold(&Rectangle::new(10, 40)));
+    assert!(!rect1.can_hold(&Rectangle::new(60, 45)));
+    // 40 x 20 only fits turned on its side.
+    assert!(rect1.can_hold(&Rectangle::new(40, 20)));
+    // Holding is strict: a rectangle cannot hold one of its own size.
+    assert!(!rect1.can_hold(&rect1));
+}
@@ -0,0 +1,104 @@
+use structs::registry::{RegistryError, UserRegistry};
+use structs::user::{User, UserError};
+
+fn user(username: &str, email: &str) -> User {
+    User::builder()
+        .username(username)
+        .email(email)
+        .build()
+        .unwrap()
+}
+
+fn registry() -> UserRegistry {
+    let mut registry = UserRegistry::new();
+    registry
+        .add(user("Elijah", "elijahgathang@gmail.com"))
+        .unwrap();
+    registry
+        .add(user("anotherusername567", "another@example.com"))
+        .unwrap();
+    registry
+}
+
+#[test]
+fn rejects_duplicate_usernames_and_emails_ignoring_case() {
+    let mut registry = registry();
+    assert_eq!(
+        registry.add(user("elijah", "new@example.com")),
+        Err(RegistryError::DuplicateUsername("elijah".into()))
+    );
+    assert_eq!(
+        registry.add(user("newcomer", "Another@Example.com")),
+        Err(RegistryError::DuplicateEmail("Another@Example.com".into()))
+    );
+    assert_eq!(registry.len(), 2);
+}
+
+#[test]
+fn looks_up_by_username_or_email() {
+    let registry = registry();
+    assert_eq!(registry.get("ELIJAH").unwrap().username(), "Elijah");
+    assert_eq!(
+        registry
+            .find_by_email("another@example.com")
+            .unwrap()
+            .username(),
+        "anotherusername567"
+    );
+    assert!(registry.get("nobody").is_none());
+}
+
+#[test]
+fn updates_email_with_validation_and_uniqueness() {
+    let mut registry = registry();
+    assert!(matches!(
+        registry.update_email("Elijah", "not-an-email"),
+        Err(RegistryError::Invalid(UserError::InvalidEmail { .. }))
+    ));
+    assert_eq!(
+        registry.update_email("Elijah", "another@example.com"),
+        Err(RegistryError::DuplicateEmail("another@example.com".into()))
+    );
+    registry
+        .update_email("Elijah", "elijah@example.org")
+        .unwrap();
+    assert_eq!(
+        registry
+            .find_by_email("elijah@example.org")
+            .unwrap()
+            .username(),
+        "Elijah"
+    );
+    assert!(registry.find_by_email("elijahgathang@gmail.com").is_none());
+    // The old address can now be used by somebody else.
+    registry
+        .add(user("gathang", "elijahgathang@gmail.com"))
+        .unwrap();
+}
+
+#[test]
+fn lists_only_active_users() {
+    let mut registry = registry();
+    registry.deactivate("anotherusername567", 0).unwrap();
+    let active: Vec<&str> = registry.active_users().map(User::username).collect();
+    assert_eq!(active, ["Elijah"]);
+    assert!(matches!(
+        registry.sign_in("anotherusername567", 10),
+        Err(RegistryError::Invalid(UserError::Inactive(_)))
+    ));
+    assert_eq!(registry.sign_in("Elijah", 10), Ok(1));
+}
+
+#[test]
+fn removing_a_user_frees_the_username_and_email() {
+    let mut registry = registry();
+    let removed = registry.remove("elijah").unwrap();
+    assert_eq!(removed.username(), "Elijah");
+    assert_eq!(
+        registry.remove("elijah"),
+        Err(RegistryError::UnknownUser("elijah".into()))
+    );
+    registry
+        .add(user("Elijah", "elijahgathang@gmail.com"))
+        .unwrap();
+}
@@ -0,0 +1,71 @@
+use structs::packing::pack_shelves;
+use structs::rectangle::Rectangle;
+use structs::render::{Shape, to_ascii, to_svg};
+
+#[test]
+fn ascii_draws_outlines_and_labels() {
+    let shapes = [
+        Shape::new(Rectangle::new(8, 6), "big"),
+        Shape::new(Rectangle::at(10, 0, 4, 2), "small"),
+    ];
+    // "small" is only one row tall, so its label goes on its top edge.
+    assert_eq!(
+        to_ascii(&shapes, 80),
+        "+-------+ +sma+\n\
+         |big    | +---+\n\
+         |       |\n\
+         +-------+\n\
+         (1 column = 1 units, 1 row = 2 units)\n"
+    );
+}
+
+#[test]
+fn ascii_scales_to_the_width() {
+    let shapes = [Shape::new(Rectangle::new(1000, 100), "wide")];
+    let drawing = to_ascii(&shapes, 40);
+    for line in drawing.lines().filter(|line| !line.starts_with('(')) {
+        assert!(line.chars().count() <= 40, "{line}");
+    }
+    assert!(drawing.ends_with("(1 column = 26 units, 1 row = 52 units)\n"));
+}
+
+#[test]
+fn ascii_of_nothing_is_empty() {
+    assert_eq!(to_ascii(&[], 80), "");
+}
+
+#[test]
+fn svg_has_a_rect_and_an_escaped_label_per_shape() {
+    let shapes = [
+        Shape::new(Rectangle::at(-10, 0, 20, 10), "a < b"),
+        Shape::new(Rectangle::at(0, 5, 40, 40), "c & d"),
+    ];
+    let svg = to_svg(&shapes);
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-12 -2 54 49\">"));
+    assert_eq!(svg.matches("<rect ").count(), 2);
+    assert!(svg.contains("<rect x=\"-10\" y=\"0\" width=\"20\" height=\"10\""));
+    assert!(svg.contains(">a &lt; b</text>"));
+    assert!(svg.contains(">c &amp; d</text>"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
+
+#[test]
+fn draws_a_packing() {
+    let items = [Rectangle::new(5, 5), Rectangle::new(10, 5)];
+    let packing = pack_shelves(&Rectangle::new(20, 10), &items);
+    let shapes = packing.shapes();
+    assert_eq!(shapes.len(), 3);
+    assert_eq!(shapes[0].label, "container");
+    assert_eq!(to_svg(&shapes).matches("<rect ").count(), 3);
+}
+
+#[test]
+fn ascii_scales_a_tall_shape_to_fit() {
+    let shapes = [Shape::new(Rectangle::new(10, u32::MAX), "tall")];
+    let drawing = to_ascii(&shapes, 40);
+    // At this scale the shape is a single column, 39 rows tall.
+    let lines: Vec<&str> = drawing.lines().collect();
+    assert_eq!(lines.len(), 40, "{drawing}");
+    assert_eq!((lines[0], lines[1], lines[38]), ("+", "|", "+"));
+    assert!(lines[39].ends_with(", 1 row = 110127368 units)"));
+}
@@ -0,0 +1,185 @@
+use structs::registry::{RegistryError, UserRegistry};
+use structs::session::{AuditEvent, AuditKind};
+use structs::user::{User, UserError};
+
+fn elijah() -> User {
+    User::builder()
+        .username("Elijah")
+        .email("elijahgathang@gmail.com")
+        .max_failed_sign_ins(3)
+        .build()
+        .unwrap()
+}
+
+fn kinds(user: &User) -> Vec<AuditKind> {
+    user.audit_log().iter().map(|event| event.kind).collect()
+}
+
+#[test]
+fn tracks_a_session_from_sign_in_to_sign_out() {
+    let mut user = elijah();
+    assert!(!user.is_signed_in());
+    assert_eq!(user.last_seen(), None);
+    assert_eq!(user.session_duration(100), None);
+
+    assert_eq!(user.sign_in(100), Ok(1));
+    assert!(user.is_signed_in());
+    assert_eq!(user.session_duration(160), Some(60));
+    user.record_activity(130).unwrap();
+    assert_eq!(user.last_seen(), Some(130));
+
+    assert_eq!(user.sign_out(400), Ok(300));
+    assert!(!user.is_signed_in());
+    assert_eq!(user.last_seen(), Some(400));
+    assert_eq!(
+        user.sign_out(500),
+        Err(UserError::NotSignedIn("Elijah".into()))
+    );
+    assert_eq!(
+        user.record_activity(500),
+        Err(UserError::NotSignedIn("Elijah".into()))
+    );
+    assert_eq!(
+        user.audit_log(),
+        [
+            AuditEvent {
+                at: 100,
+                kind: AuditKind::SignedIn
+            },
+            AuditEvent {
+                at: 400,
+                kind: AuditKind::SignedOut {
+                    session_seconds: 300
+                }
+            },
+        ]
+    );
+}
+
+#[test]
+fn signing_in_again_closes_the_open_session() {
+    let mut user = elijah();
+    user.sign_in(0).unwrap();
+    assert_eq!(user.sign_in(50), Ok(2));
+    assert_eq!(user.session_duration(60), Some(10));
+    assert_eq!(
+        kinds(&user),
+        [
+            AuditKind::SignedIn,
+            AuditKind::SignedOut {
+                session_seconds: 50
+            },
+            AuditKind::SignedIn
+        ]
+    );
+}
+
+#[test]
+fn locks_after_too_many_failed_sign_ins() {
+    let mut user = elijah();
+    assert_eq!(user.failed_sign_in(1), 1);
+    // A successful sign-in starts the count over.
+    user.sign_in(2).unwrap();
+    assert_eq!(user.failed_sign_ins(), 0);
+
+    assert_eq!(user.failed_sign_in(3), 1);
+    assert_eq!(user.failed_sign_in(4), 2);
+    assert!(!user.is_locked());
+    assert_eq!(user.failed_sign_in(5), 3);
+    assert!(user.is_locked());
+    // Locking ends the session that was open.
+    assert!(!user.is_signed_in());
+    assert_eq!(
+        user.to_string(),
+        "Elijah <elijahgathang@gmail.com> (locked)"
+    );
+
+    assert_eq!(user.sign_in(6), Err(UserError::Locked("Elijah".into())));
+    user.unlock(7);
+    assert_eq!(user.failed_sign_ins(), 0);
+    assert_eq!(user.sign_in(8), Ok(2));
+    assert_eq!(
+        kinds(&user)[2..],
+        [
+            AuditKind::SignInFailed,
+            AuditKind::SignInFailed,
+            AuditKind::SignInFailed,
+            AuditKind::SignedOut { session_seconds: 3 },
+            AuditKind::Locked,
+            AuditKind::SignInFailed,
+            AuditKind::Unlocked,
+            AuditKind::SignedIn,
+        ]
+    );
+}
+
+#[test]
+fn zero_attempts_never_locks() {
+    let mut user = User::builder()
+        .username("patient")
+        .email("p@example.com")
+        .max_failed_sign_ins(0)
+        .build()
+        .unwrap();
+    for second in 0..100 {
+        user.failed_sign_in(second);
+    }
+    assert!(!user.is_locked());
+    assert_eq!(user.failed_sign_ins(), 100);
+}
+
+#[test]
+fn deactivating_signs_out_and_is_audited() {
+    let mut user = elijah();
+    user.sign_in(10).unwrap();
+    user.deactivate(70);
+    assert!(!user.is_signed_in());
+    user.activate(80);
+    assert_eq!(
+        kinds(&user),
+        [
+            AuditKind::SignedIn,
+            AuditKind::SignedOut {
+                session_seconds: 60
+            },
+            AuditKind::Deactivated,
+            AuditKind::Activated
+        ]
+    );
+}
+
+#[test]
+fn registry_tracks_sessions_per_user() {
+    let mut registry = UserRegistry::new();
+    registry.add(elijah()).unwrap();
+    registry
+        .add(
+            User::builder()
+                .username("alice")
+                .email("a@example.com")
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+    registry.sign_in("elijah", 0).unwrap();
+    registry.sign_in("alice", 5).unwrap();
+    assert_eq!(registry.sign_out("alice", 25), Ok(20));
+    let signed_in: Vec<&str> = registry.signed_in_users().map(User::username).collect();
+    assert_eq!(signed_in, ["Elijah"]);
+    assert_eq!(registry.audit_log("ALICE").unwrap().len(), 2);
+
+    for second in 1..=3 {
+        registry.failed_sign_in("elijah", second).unwrap();
+    }
+    assert_eq!(
+        registry.sign_in("elijah", 4),
+        Err(RegistryError::Invalid(UserError::Locked("Elijah".into())))
+    );
+    registry.unlock("elijah", 5).unwrap();
+    assert_eq!(registry.sign_in("elijah", 6), Ok(2));
+    assert_eq!(
+        registry.audit_log("bob"),
+        Err(RegistryError::UnknownUser("bob".into()))
+    );
+}
@@ -0,0 +1,105 @@
+use structs::user::{User, UserError, validate_email, validate_username};
+
+fn elijah() -> User {
+    User::builder()
+        .username("Elijah")
+        .email("elijahgathang@gmail.com")
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn builds_an_active_user_that_has_not_signed_in() {
+    let user = elijah();
+    assert_eq!(user.username(), "Elijah");
+    assert_eq!(user.email(), "elijahgathang@gmail.com");
+    assert_eq!(user.sign_in_count(), 0);
+    assert!(user.is_active());
+}
+
+#[test]
+fn requires_username_and_email() {
+    assert_eq!(
+        User::builder().email("a@example.com").build(),
+        Err(UserError::MissingUsername)
+    );
+    assert_eq!(
+        User::builder().username("alice").build(),
+        Err(UserError::MissingEmail)
+    );
+}
+
+#[test]
+fn checks_username_rules() {
+    assert!(validate_username("anotherusername567").is_ok());
+    assert!(validate_username("jo.doe-2_x").is_ok());
+    assert_eq!(
+        validate_username("ab"),
+        Err(UserError::UsernameTooShort { length: 2 })
+    );
+    assert_eq!(
+        validate_username(&"a".repeat(33)),
+        Err(UserError::UsernameTooLong { length: 33 })
+    );
+    assert_eq!(
+        validate_username("9lives"),
+        Err(UserError::UsernameStartsWithNonLetter('9'))
+    );
+    assert_eq!(
+        validate_username("john doe"),
+        Err(UserError::UsernameInvalidChar(' '))
+    );
+}
+
+#[test]
+fn checks_email_syntax() {
+    for good in [
+        "another@example.com",
+        "first.last+tag@mail.example.co.uk",
+        "x@a-b.io",
+    ] {
+        assert!(validate_email(good).is_ok(), "{good}");
+    }
+    for bad in [
+        "",
+        "plainaddress",
+        "@example.com",
+        "a@b@example.com",
+        ".a@example.com",
+        "a..b@example.com",
+        "a@localhost",
+        "a@-example.com",
+        "a@example..com",
+        "a@example.c0m",
+        "a b@example.com",
+    ] {
+        assert!(
+            matches!(validate_email(bad), Err(UserError::InvalidEmail { .. })),
+            "{bad}"
+        );
+    }
+}
+
+#[test]
+fn sign_in_counts_until_deactivated() {
+    let mut user = elijah();
+    assert_eq!(user.sign_in(10), Ok(1));
+    assert_eq!(user.sign_in(20), Ok(2));
+    user.deactivate(30);
+    assert_eq!(user.sign_in(40), Err(UserError::Inactive("Elijah".into())));
+    assert_eq!(user.sign_in_count(), 2);
+    user.activate(50);
+    assert_eq!(user.sign_in(60), Ok(3));
+}
+
+#[test]
+fn sign_in_count_stops_at_the_largest_count() {
+    let mut user = User::builder()
+        .username("Elijah")
+        .email("e@example.com")
+        .sign_in_count(u64::MAX)
+        .build()
+        .unwrap();
+    assert_eq!(user.sign_in(10), Ok(u64::MAX));
+    assert_eq!(user.sign_in_count(), u64::MAX);
+}